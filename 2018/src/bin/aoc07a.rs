@@ -14,19 +14,14 @@
 
 //! https://adventofcode.com/2018/day/7
 //!
-//! The input is small enough we can use a somewhat brute-force algorithm:
-//!
-//! Parse the input into a sorted map from not-yet-executed postconditions,
-//! to a set of as-yet-unmet preconditions.
-//!
-//! Walk that map to find the alphabetically first step that has no unmet
-//! preconditions. Emit it, and then go through the map and remove it from
-//! all precondition sets.
+//! Parse the input into a graph with an edge from each step to the steps
+//! that depend on it. The order is a topological sort of that graph, taking
+//! the alphabetically first step whenever there's a choice.
 
-use std::collections::{BTreeMap, BTreeSet};
 use std::io;
 use std::io::prelude::*;
 
+use aoclib::graph::Graph;
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -36,12 +31,10 @@ pub fn main() {
     println!("order: {:?}", cs.find_order());
 }
 
-type Step = char;
-
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 struct Constraints {
-    /// From postcondition to set of preconditions
-    deps: BTreeMap<Step, BTreeSet<Step>>,
+    /// Edges from each precondition to the steps that depend on it.
+    deps: Graph<(), ()>,
 }
 
 impl Constraints {
@@ -52,15 +45,17 @@ impl Constraints {
                     .unwrap();
         }
 
-        let mut deps = BTreeMap::new();
+        let mut deps = Graph::directed();
         for l in s {
             if let Some(cap) = STEP_RE.captures(l.as_ref()) {
-                let pre = cap.get(1).unwrap().as_str().chars().next().unwrap();
-                let post = cap.get(2).unwrap().as_str().chars().next().unwrap();
-                let e = deps.entry(post).or_insert_with(BTreeSet::new);
-                assert!(e.insert(pre), "pair {post:?} {pre:?} already present?");
-                // We also know the precondition exists
-                deps.entry(pre).or_insert_with(BTreeSet::new);
+                let (pre_name, post_name) = (&cap[1], &cap[2]);
+                let pre = deps.node(pre_name);
+                let post = deps.node(post_name);
+                assert!(
+                    deps.neighbors(pre).all(|(n, _)| n != post),
+                    "pair {post_name:?} {pre_name:?} already present?"
+                );
+                deps.add_edge(pre, post, ());
             } else {
                 panic!("Can't parse {:?}", l.as_ref());
             }
@@ -70,21 +65,12 @@ impl Constraints {
     }
 
     pub fn find_order(&self) -> String {
-        let mut s = String::new();
-        let mut deps = self.deps.clone();
-        while !deps.is_empty() {
-            let next = *deps
-                .iter()
-                .filter_map(|(k, v)| if v.is_empty() { Some(k) } else { None })
-                .next()
-                .unwrap();
-            deps.remove(&next);
-            for v in deps.values_mut() {
-                v.remove(&next);
-            }
-            s.push(next);
-        }
-        s
+        self.deps
+            .toposort()
+            .expect("constraints have a cycle")
+            .into_iter()
+            .map(|n| self.deps.name(n))
+            .collect()
     }
 }
 
@@ -14,7 +14,7 @@
 
 //! https://adventofcode.com/2018/day/11
 
-use mbpaoc2018::{point, Matrix};
+use aoclib::Matrix;
 
 // Performance can probably be improved by remembering the sum of some
 // (aligned? even sized?) blocks and using them when computing the sum of
@@ -42,7 +42,7 @@ pub fn main() {
 }
 
 struct Map {
-    /// Power levels indexed by `point(x, y)`.
+    /// Power levels indexed by `(x, y)`.
     /// In the problem description indices are 1-based but for simplicity
     /// these are 1-based, and we convert on output.
     p: Matrix<i32>,
@@ -68,7 +68,7 @@ impl Map {
                 pwr = (pwr / 100) % 10;
                 // Subtract 5 from the power level.
                 pwr -= 5;
-                p[point(x, y)] = pwr;
+                p[(x, y)] = pwr;
             }
         }
         Map { p }
@@ -76,14 +76,14 @@ impl Map {
 
     #[cfg(test)]
     pub fn get(&self, c: (usize, usize)) -> i32 {
-        self.p[point(c.0, c.1)]
+        self.p[(c.0, c.1)]
     }
 
     pub fn squaresum(&self, c: (usize, usize), sqsz: usize) -> i32 {
         let mut s: i32 = 0;
         for x in c.0..(c.0 + sqsz) {
             for y in c.1..(c.1 + sqsz) {
-                s += self.p[point(x, y)];
+                s += self.p[(x, y)];
             }
         }
         s
@@ -135,14 +135,14 @@ impl Map {
         let mut sqs = Matrix::new(SIZE, SIZE, 0i32);
         let mut vstr = Matrix::new(SIZE, SIZE, 0i32);
         let mut hstr = Matrix::new(SIZE, SIZE, 0i32);
-        let mut best_p = point(0, 0);
+        let mut best_p = (0, 0);
         let mut best_power = i32::MIN;
         let mut best_size = 1;
 
         // Start at size 1: everything is simply the contents of that cell.
         for x in 0..SIZE {
             for y in 0..SIZE {
-                let p = point(x, y);
+                let p = (x, y);
                 vstr[p] = self.p[p];
                 hstr[p] = self.p[p];
                 sqs[p] = self.p[p];
@@ -162,10 +162,8 @@ impl Map {
             let osz = sz - 1;
             for x in 0..(SIZE - sz) {
                 for y in 0..(SIZE - sz) {
-                    let p = point(x, y);
-                    sqs[p] += vstr[point(x + osz, y)]
-                        + hstr[point(x, y + osz)]
-                        + self.p[point(x + osz, y + osz)];
+                    let p = (x, y);
+                    sqs[p] += vstr[(x + osz, y)] + hstr[(x, y + osz)] + self.p[(x + osz, y + osz)];
                     if sqs[p] > best_power {
                         best_power = sqs[p];
                         best_p = p;
@@ -178,14 +176,14 @@ impl Map {
             // by adding one more square.
             for x in 0..(SIZE - sz) {
                 for y in 0..(SIZE - sz) {
-                    let p = point(x, y);
-                    vstr[p] += self.p[point(x, y + osz)];
-                    hstr[p] += self.p[point(x + osz, y)];
+                    let p = (x, y);
+                    vstr[p] += self.p[(x, y + osz)];
+                    hstr[p] += self.p[(x + osz, y)];
                 }
             }
         }
 
-        ((best_p.x + 1, best_p.y + 1), best_size, best_power)
+        ((best_p.0 + 1, best_p.1 + 1), best_size, best_power)
     }
 }
 
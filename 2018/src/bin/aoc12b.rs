@@ -28,40 +28,30 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::rc::Rc;
 
+use aoclib::cycle::find_cycle;
+
 pub fn main() {
+    println!("result = {}", solve_b(&load_input()));
+}
+
+fn load_input() -> String {
     let mut s = String::new();
     File::open("input/input12.txt")
         .unwrap()
         .read_to_string(&mut s)
         .unwrap();
-    let mut p = Pots::from_string(&s);
-    let n_steps = 50_000_000_000u64;
-    let mut i = 0;
-    loop {
-        // 0.. {
-        println!("left={}  {}", p.left(), p.format_pots(p.left()..=p.right()));
-        let next_p = p.step();
-        // if i % 100_000 == 0 {
-        //     println!("i={:>10} p={:?}", i, p);
-        // }
-        // if next_p == p {
-        //     break
-        // } else {
-        //     p = next_p
-        // }
-        if next_p.is_right_shift(&p) {
-            println!("found stable right shift");
-            break;
-        }
-        p = next_p;
-        i += 1;
-    }
-    // The sum of number of pots that have a plant is, the current sum, plus the remaining steps times
-    // the number of pots.
-    println!(
-        "result = {}",
-        p.magic() as u64 + (n_steps - i) * p.pots.len() as u64
-    );
+    s
+}
+
+fn solve_b(s: &str) -> i64 {
+    let n_steps = 50_000_000_000;
+    // The pattern eventually stops changing shape and just drifts along,
+    // so the sum of pot numbers changes by the same amount every cycle.
+    let history = find_cycle(Pots::from_string(s), Pots::step, |p| {
+        let left = p.left();
+        p.pots.iter().map(|i| i - left).collect::<Vec<_>>()
+    });
+    history.extrapolate(n_steps, |p| p.magic() as i64)
 }
 
 fn from_b(c: u8) -> bool {
@@ -182,19 +172,6 @@ impl Pots {
     pub fn magic(&self) -> isize {
         self.pots.iter().sum()
     }
-
-    /// True if self is `other` shifted one space to the right.
-    pub fn is_right_shift(&self, other: &Pots) -> bool {
-        if self.pots.len() != other.pots.len() {
-            return false;
-        }
-        for i in self.pots.iter() {
-            if !other.pots.contains(&(i - 1)) {
-                return false;
-            }
-        }
-        true
-    }
 }
 
 #[cfg(test)]
@@ -250,6 +227,11 @@ initial state: #..#.#..##......###...###
         assert_eq!(p1.magic(), 325);
     }
 
+    #[test]
+    fn solution_b() {
+        assert_eq!(solve_b(&load_input()), 3900000002212);
+    }
+
     #[test]
     fn stable() {
         let p = Pots::from_string("\
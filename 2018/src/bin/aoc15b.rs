@@ -19,7 +19,7 @@
 use std::fs::File;
 use std::io::Read;
 
-use mbpaoc2018::{point, Matrix, Point};
+use aoclib::{Matrix, Point};
 
 const INITIAL_HP: usize = 200;
 const GOBLIN_POWER: usize = 3;
@@ -151,22 +151,10 @@ impl Map {
 
     /// Return all valid neighbors of a point, in reading order.
     pub fn neighbors(&self, p: Point) -> Vec<Point> {
-        let mut v = Vec::with_capacity(4);
-        let x = p.x;
-        let y = p.y;
-        if y > 0 {
-            v.push(Point { x, y: y - 1 })
-        }
-        if x > 0 {
-            v.push(Point { x: x - 1, y })
-        }
-        if x < self.w - 1 {
-            v.push(Point { x: x + 1, y })
-        }
-        if y < self.h - 1 {
-            v.push(Point { x, y: y + 1 })
-        }
-        v
+        [p.up(), p.left(), p.right(), p.down()]
+            .into_iter()
+            .filter(|q| self.m.contains_point(*q))
+            .collect()
     }
 
     /// Return the empty neighbors of a point, in reading order.
@@ -248,7 +236,7 @@ impl Map {
         let mut s = format!("Round: {}\n", self.completed_rounds);
         for y in 0..self.h {
             for x in 0..self.w {
-                let p = point(x, y);
+                let p = Point::from_usizes(x, y);
                 s.push(self.thing_at(p).to_char());
             }
             s.push('\n');
@@ -277,7 +265,7 @@ impl Map {
         let mut recently_moved: Vec<Point> = Vec::new();
         for y in 0..self.h {
             for x in 0..self.w {
-                let cp = point(x, y);
+                let cp = Point::from_usizes(x, y);
                 let th = self.thing_at(cp);
                 if !th.is_creature() {
                     continue;
@@ -323,7 +311,10 @@ impl Map {
         let mut remain_hp = 0;
         for y in 0..self.h {
             for x in 0..self.w {
-                remain_hp += self.thing_at(point(x, y)).creature_hp().unwrap_or(0)
+                remain_hp += self
+                    .thing_at(Point::from_usizes(x, y))
+                    .creature_hp()
+                    .unwrap_or(0)
             }
         }
         (
@@ -401,13 +392,13 @@ impl Routing {
             last = next;
         }
 
-        // Chosen destination is the first one, in order.
+        // Chosen destination is the first one, in reading order.
         if ends.is_empty() {
             // Filled as much of the map as we can, without finding any
             // reachable enemies. Let's stop.
             return None;
         };
-        let chosen = *ends.iter().min().unwrap();
+        let chosen = *ends.iter().min_by_key(|p| (p.y, p.x)).unwrap();
 
         // Now walk back from that chosen point towards the origin, taking at each
         // step the smallest numbered point. Since we only ever numbered
@@ -438,6 +429,8 @@ impl Routing {
 
 #[cfg(test)]
 mod test {
+    use aoclib::point;
+
     use super::*;
 
     #[test]
@@ -18,7 +18,7 @@ use std::cmp::{max, min};
 
 use regex::Regex;
 
-use mbpaoc2018::{point, Matrix, Point};
+use aoclib::{point, Matrix, Point};
 
 // Read the input lines and draw into a matrix. Maybe pre-scan to work out the
 // maximum dimensions.
@@ -138,10 +138,10 @@ impl Line {
 pub struct Map {
     m: Matrix<Thing>,
     drip: Vec<Point>,
-    x_min: usize,
-    x_max: usize,
-    y_min: usize,
-    y_max: usize,
+    x_min: isize,
+    x_max: isize,
+    y_min: isize,
+    y_max: isize,
 }
 
 impl Map {
@@ -153,26 +153,26 @@ impl Map {
             match *l {
                 Line::Vertical { x, y1, y2 } => {
                     for y in y1..=y2 {
-                        m[point(x, y)] = Clay;
+                        m[(x, y)] = Clay;
                     }
                 }
                 Line::Horizontal { x1, x2, y } => {
                     for x in x1..=x2 {
-                        m[point(x, y)] = Clay;
+                        m[(x, y)] = Clay;
                     }
                 }
             }
         }
         println!("Created map; xrange={x_min}..={x_max}, yrange={y_min}..={y_max}");
         // Skip from (500, 0) down to the first point on the map.
-        let drip1 = point(500, y_min);
+        let drip1 = Point::from_usizes(500, y_min);
         let mut map = Map {
             m,
             drip: vec![],
-            x_min,
-            x_max,
-            y_min,
-            y_max,
+            x_min: x_min as isize,
+            x_max: x_max as isize,
+            y_min: y_min as isize,
+            y_max: y_max as isize,
         };
         map.add_drip(drip1);
         map
@@ -14,7 +14,7 @@
 
 #![allow(dead_code)]
 
-use mbpaoc2018::*;
+use aoclib::Matrix;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Thing {
@@ -62,7 +62,7 @@ impl Map {
         let mut s = String::with_capacity(self.m.height() * (self.m.width() + 1));
         for y in 0..self.m.height() {
             for x in 0..self.m.width() {
-                s.push(self.m[point(x, y)].to_char())
+                s.push(self.m[(x, y)].to_char())
             }
             s.push('\n')
         }
@@ -71,7 +71,7 @@ impl Map {
 
     pub fn step(&self) -> Map {
         let mut newm = Matrix::new(self.m.width(), self.m.height(), Open);
-        for p in self.m.iter_points() {
+        for p in self.m.points() {
             let ns = self.m.neighbor8_values(p);
             newm[p] = match self.m[p] {
                 Open => {
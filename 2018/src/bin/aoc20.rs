@@ -17,82 +17,10 @@
 //! https://adventofcode.com/2018/day/20
 
 use std::collections::BTreeSet;
-use std::fmt;
 use std::fs::File;
 use std::io::prelude::*;
 
-type Coord = i32;
-#[derive(PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
-struct Point {
-    x: Coord,
-    y: Coord,
-}
-
-impl Point {
-    #[allow(unused)]
-    pub fn update(&mut self, dir: Dir) {
-        *self = self.step(dir);
-    }
-
-    pub fn step(self, dir: Dir) -> Point {
-        let mut n = self;
-        match dir {
-            Dir::N => n.y -= 1,
-            Dir::S => n.y += 1,
-            Dir::E => n.x += 1,
-            Dir::W => n.x -= 1,
-        };
-        n
-    }
-
-    pub fn origin() -> Point {
-        Point { x: 0, y: 0 }
-    }
-}
-
-#[allow(unused)]
-fn pt(x: Coord, y: Coord) -> Point {
-    Point { x, y }
-}
-
-impl fmt::Debug for Point {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum Dir {
-    N,
-    S,
-    E,
-    W,
-}
-
-#[allow(unused)]
-static DIRECTIONS: [Dir; 4] = [Dir::N, Dir::S, Dir::E, Dir::W];
-
-impl Dir {
-    fn from_char(c: char) -> Dir {
-        match c {
-            'N' => Dir::N,
-            'S' => Dir::S,
-            'E' => Dir::E,
-            'W' => Dir::W,
-            other => panic!("unexpected character {other:?}"),
-        }
-    }
-
-    #[allow(unused)]
-    fn to_char(self) -> char {
-        match self {
-            Dir::N => 'N',
-            Dir::S => 'S',
-            Dir::E => 'E',
-            Dir::W => 'W',
-        }
-    }
-}
+use aoclib::{point, Dir, Point};
 
 /// Map of rooms that have been visited.
 ///
@@ -151,7 +79,7 @@ impl Map {
         let mut seen = BTreeSet::new();
         let mut next = BTreeSet::new();
         let mut far_count = 0;
-        next.insert(Point::origin());
+        next.insert(point(0, 0));
         loop {
             // println!("depth {}, seen={:?}, next={:?}", depth, seen, next);
             let mut new_rooms = BTreeSet::new();
@@ -184,7 +112,7 @@ impl Map {
         let mut depth = 0;
         let mut seen = BTreeSet::new();
         let mut next = BTreeSet::new();
-        next.insert(Point::origin());
+        next.insert(point(0, 0));
         loop {
             // println!("depth {}, seen={:?}, next={:?}", depth, seen, next);
             let mut new_rooms = BTreeSet::new();
@@ -242,12 +170,12 @@ fn expand(r: &str) -> Map {
 
     // Currently-live turtle positions, for the current branch.
     let mut turs = BTreeSet::new();
-    turs.insert(Point::origin());
+    turs.insert(point(0, 0));
 
     for c in r.chars() {
         match c {
             'N' | 'E' | 'S' | 'W' => {
-                let dir = Dir::from_char(c);
+                let dir = Dir::from_char(c).unwrap();
                 let mut newturs = BTreeSet::new();
                 for t in turs {
                     map.record_move(t, dir);
@@ -331,8 +259,7 @@ pub fn main() {
 
 #[cfg(test)]
 mod test {
-    use super::pt;
-    use super::Point;
+    use aoclib::{point, Point};
 
     #[test]
     fn test_load_input() {
@@ -372,22 +299,28 @@ mod test {
         assert_eq!(
             e_doors,
             vec![
-                pt(-2, -2),
-                pt(-2, -1),
-                pt(-2, 1),
-                pt(-1, -2),
-                pt(-1, -1),
-                pt(-1, 1),
-                pt(0, -2),
-                pt(0, -1),
-                pt(0, 0),
-                pt(0, 1)
+                point(-2, -2),
+                point(-2, -1),
+                point(-2, 1),
+                point(-1, -2),
+                point(-1, -1),
+                point(-1, 1),
+                point(0, -2),
+                point(0, -1),
+                point(0, 0),
+                point(0, 1)
             ]
         );
         let n_doors: Vec<Point> = map.n_doors.iter().cloned().collect();
         assert_eq!(
             n_doors,
-            vec![pt(-2, -1), pt(-2, 0), pt(-2, 1), pt(-1, 1), pt(1, 0),]
+            vec![
+                point(-2, -1),
+                point(-2, 0),
+                point(-2, 1),
+                point(-1, 1),
+                point(1, 0),
+            ]
         );
         assert_eq!(map.furthest(), 10);
     }
@@ -18,11 +18,12 @@
 
 use std::collections::BTreeMap;
 
-use mbpaoc2018::{point, shortest_distance, Point};
+use aoclib::shortest_path::ShortestPath;
+use aoclib::{point, Point};
 
 type Erosion = usize;
 
-#[derive(Clone, Copy, Debug, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialOrd, Ord, PartialEq, Eq, Hash)]
 enum Tool {
     Climbing,
     Torch,
@@ -59,7 +60,7 @@ struct Map {
 }
 
 /// Combination of a location, and a tool.
-#[derive(Debug, Clone, Copy, PartialOrd, Ord, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, Eq, PartialEq, Hash)]
 struct State {
     p: Point,
     t: Tool,
@@ -78,13 +79,14 @@ impl Map {
         if let Some(e) = self.e.get(&p) {
             return *e;
         }
+        let (x, y) = p.to_usizes();
         let v = if p == self.target {
             0
-        } else if p.y == 0 {
+        } else if y == 0 {
             // This also handles the (0,0) case.
-            p.x * 16807
-        } else if p.x == 0 {
-            p.y * 48271
+            x * 16807
+        } else if x == 0 {
+            y * 48271
         } else {
             let v1 = self.erosion_at(p.left());
             let v2 = self.erosion_at(p.up());
@@ -141,6 +143,7 @@ impl Map {
         r.extend(
             st.p.neighbors()
                 .iter()
+                .filter(|np| np.x >= 0 && np.y >= 0)
                 .filter(|np| legal(t, self.ground_at(**np)))
                 .map(|np| (State { t, p: *np }, 1)),
         );
@@ -159,8 +162,13 @@ impl Map {
             p: self.target,
             t: Torch,
         };
-        // shortest_distance(start, dest, &mut |state| self.neighbors(state))
-        shortest_distance(start, dest, &mut |state| self.neighbors(state))
+        ShortestPath::find(
+            &start,
+            |state| *state == dest,
+            |state| self.neighbors(*state),
+        )
+        .expect("target is reachable")
+        .distance()
     }
 }
 
@@ -191,7 +199,7 @@ pub fn main() {
 #[cfg(test)]
 mod test {
     use super::Map;
-    use mbpaoc2018::point;
+    use aoclib::point;
 
     #[test]
     fn build_map() {
@@ -20,20 +20,20 @@
 //! Really, this is about finding intersections between Manhattan-distance
 //! diamond shapes in 3d space.
 //!
-//! The basic problem is NP-hard, and n=1000, so the challenge here is to get
-//! some reasonable approximation that we can actually compute.
+//! The basic problem is NP-hard, and n=1000, so the challenge here is to
+//! avoid looking at every point or every combination of bots. Part B
+//! searches an octree of boxes, splitting the most promising box first.
 
 extern crate itertools;
 extern crate regex;
 
-use std::cmp::{max, min};
-use std::collections::BTreeSet;
+use std::cmp::{min, Reverse};
 use std::fs::File;
 use std::io::Read;
 
 use regex::Regex;
 
-type Coord = (isize, isize, isize);
+use aoclib::{Cuboid, KeyedHeap};
 
 /// The location and radius of one nanobot.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -45,6 +45,15 @@ struct Bot {
 }
 
 impl Bot {
+    fn pos(&self) -> [isize; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// True if any point in `c` is in range of this bot.
+    fn reaches(&self, c: &Cuboid<3>) -> bool {
+        distance_to_cuboid(self.pos(), c) <= self.r
+    }
+
     fn zone(&self) -> Zone {
         Zone {
             pxpypz: self.x + self.y + self.z + self.r,
@@ -150,6 +159,7 @@ impl Zone {
             && (-b.0 - b.1 - b.2) <= self.mxmymz
     }
 
+    #[allow(unused)]
     fn intersect(&self, other: &Zone) -> Zone {
         Zone {
             pxpypz: min(self.pxpypz, other.pxpypz),
@@ -162,73 +172,6 @@ impl Zone {
             mxmymz: min(self.mxmymz, other.mxmymz),
         }
     }
-
-    /// True if the constraints on this zone imply it covers no space.
-    fn is_empty(&self) -> bool {
-        (self.pxpypz + self.mxmymz) < 0
-            || (self.pxpymz + self.mxmypz) < 0
-            || (self.pxmypz + self.mxpymz) < 0
-            || (self.pxmymz + self.mxpypz) < 0
-    }
-
-    /// Return a coordinate in this zone that's closest to the origin.
-    fn closest_to_origin(&self) -> Coord {
-        let xmax1 = (self.pxpypz + self.pxmymz) / 2;
-        println!("x <= {xmax1}");
-        let xmax2 = (self.pxmypz + self.pxpymz) / 2;
-        println!("x <= {xmax2}");
-        let xmin1 = -(self.mxpypz + self.mxmymz) / 2;
-        println!("x >= {xmin1}");
-        let xmin2 = -(self.mxmypz + self.mxpymz) / 2;
-        println!("x >= {xmin2}");
-
-        if xmin1 < 0 || xmin2 < 0 {
-            // If negative we ought to look at the maximums.
-            unimplemented!();
-        }
-        let x = max(xmin1, xmin2);
-        println!("therefore x={x}");
-
-        // x + y + z <= pxpypz
-        // x + y - z <= pxpymz
-        // 2y <= pxpypz + pxpymz - 2x
-        let ymax1 = (self.pxpypz + self.pxpymz) / 2 - x;
-        let ymax2 = (self.mxpypz + self.mxpymz) / 2 + x;
-        dbg!(ymax1, ymax2);
-
-        // x - y + z <= pxmypz
-        // x - y - z <= pxmymz
-        // 2x -2y <= pxmypz + pxmymz
-        // -2y <= pxmypz + pxmymz - 2x
-        // y >= -(pxmypz + pxmymz) / 2 + x
-        let ymin1 = -(self.pxmypz + self.pxmymz) / 2 + x;
-        let ymin2 = -(self.mxmypz + self.mxmymz) / 2 - x;
-        dbg!(ymin1, ymin2);
-        if ymin1 < 0 || ymin2 < 0 {
-            unimplemented!();
-        }
-        let y = max(ymin1, ymin2);
-        dbg!(y);
-
-        // -x -y -z <= mxmymz
-        // -z <= mxmymz + x + y
-        // z >= -mxmymz - x - y
-        let zmin1 = -self.mxmymz - x - y;
-        // x + y - z <= pxpymz
-        // -z <= pxpymz - x - y
-        // z >= -pxpymz + x + y
-        let zmin2 = -self.pxpymz + x + y;
-        dbg!(zmin1, zmin2);
-        let z = max(zmin1, zmin2);
-
-        let p = (x, y, z);
-        dbg!(p);
-        dbg!(p.0.abs() + p.1.abs() + p.2.abs());
-
-        assert!(self.contains_point(p));
-
-        p
-    }
 }
 
 /// Solve part A from real input.
@@ -236,71 +179,53 @@ fn solve_a() -> usize {
     count_in_range(&load_input())
 }
 
-fn distance_from_origin(p: Coord) -> isize {
-    p.0.abs() + p.1.abs() + p.2.abs()
+/// Manhattan distance from `p` to the nearest point in `c`.
+fn distance_to_cuboid(p: [isize; 3], c: &Cuboid<3>) -> isize {
+    (0..3)
+        .map(|i| {
+            if p[i] < c.min()[i] {
+                c.min()[i] - p[i]
+            } else if p[i] >= c.max()[i] {
+                p[i] - (c.max()[i] - 1)
+            } else {
+                0
+            }
+        })
+        .sum()
 }
 
-fn solve_b() -> isize {
-    let bots = load_input();
-
-    // Make a list of, for each bot, the identities of other bots that touch it.
-    let mut touchs: Vec<BTreeSet<usize>> = vec![Default::default(); bots.len()];
-
-    for (i, a) in bots.iter().enumerate() {
-        for (j, b) in bots.iter().enumerate() {
-            if !a.zone().intersect(&b.zone()).is_empty() {
-                touchs[i].insert(j);
-            }
+/// Find the point in range of the most bots, and the closest to the origin
+/// among those, and return its distance from the origin.
+///
+/// This searches an octree, always splitting the box that reaches the most
+/// bots: that's an upper bound for every point inside it, so the first single
+/// point to come out of the queue is the best.
+fn best_distance(bots: &[Bot]) -> isize {
+    let bounds = Cuboid::from_inclusive(
+        [0, 1, 2].map(|i| bots.iter().map(|b| b.pos()[i] - b.r).min().unwrap()),
+        [0, 1, 2].map(|i| bots.iter().map(|b| b.pos()[i] + b.r).max().unwrap()),
+    );
+    // Prefer boxes reaching more bots, then closer to the origin, then smaller.
+    let priority = |c: &Cuboid<3>| {
+        let reached = bots.iter().filter(|b| b.reaches(c)).count();
+        let size = (0..3).map(|i| c.max()[i] - c.min()[i]).max().unwrap();
+        (Reverse(reached), distance_to_cuboid([0, 0, 0], c), size)
+    };
+    let mut queue = KeyedHeap::new();
+    queue.push(bounds, priority(&bounds));
+    while let Some((c, (_, distance, size))) = queue.pop() {
+        if size == 1 {
+            return distance;
+        }
+        for child in c.subdivide() {
+            queue.push(child, priority(&child));
         }
     }
+    unreachable!("the search space is never exhausted")
+}
 
-    // Find the largest `m` such that there are at least `m` bots that each intersect
-    // at least `m` bots.
-    let mut tc: Vec<_> = touchs.iter().map(|t| t.len()).collect();
-    tc.sort();
-    let m = tc
-        .iter()
-        .filter(|i| tc.iter().filter(|t| t >= i).count() >= **i)
-        .max()
-        .expect("Found no maximum likely clique");
-    dbg!(m);
-
-    // Find the specific bots that touch at least `m` bots.
-    let included_bots: Vec<Bot> = touchs
-        .iter()
-        .enumerate()
-        .filter(|(_b, t)| t.len() >= *m)
-        .map(|(b, _t)| bots[b])
-        .collect();
-
-    // (I'm not sure this necessarily must be true, but it is true on this input.)
-    assert_eq!(included_bots.len(), *m);
-
-    let excluded_bots: Vec<Bot> = bots
-        .iter()
-        .filter(|b| !included_bots.contains(b))
-        .copied()
-        .collect();
-    assert_eq!(excluded_bots.len(), bots.len() - m);
-
-    // Now (hopefully) find a region that's common between all those bots.
-    let intersection_zone = included_bots
-        .iter()
-        .fold(None, |ac, bot| match ac {
-            None => Some(bot.zone()),
-            Some(z) => Some(bot.zone().intersect(&z)),
-        })
-        .unwrap();
-    dbg!(intersection_zone);
-
-    // Let's check none of the excluded bots overlap with this region. It doesn't prove
-    // it's the largest possible region, but it does prove it overlaps with exactly
-    // `m` bots.
-    assert!(excluded_bots
-        .iter()
-        .all(|b| b.zone().intersect(&intersection_zone).is_empty()));
-
-    distance_from_origin(intersection_zone.closest_to_origin())
+fn solve_b() -> isize {
+    best_distance(&load_input())
 }
 
 pub fn main() {
@@ -367,6 +292,19 @@ mod tests {
         assert_eq!(super::solve_b(), 82010396);
     }
 
+    #[test]
+    fn example_b() {
+        let t = "\
+            pos=<10,12,12>, r=2
+            pos=<12,14,12>, r=2
+            pos=<16,12,12>, r=4
+            pos=<14,14,14>, r=6
+            pos=<50,50,50>, r=200
+            pos=<10,10,10>, r=5\
+            ";
+        assert_eq!(super::best_distance(&super::parse(t)), 36);
+    }
+
     #[test]
     fn test_intersect() {
         let v = "\
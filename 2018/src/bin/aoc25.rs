@@ -16,6 +16,8 @@
 //!
 //! Find constellations based on 4d Manhattan distance between points.
 
+use aoclib::UnionFind;
+
 type Point = [isize; 4];
 const NEAR: isize = 3;
 
@@ -45,49 +47,24 @@ fn load_input() -> Vec<Point> {
     parse_string(&std::fs::read_to_string("input/input25.txt").unwrap())
 }
 
-fn near_constellation(p: &Point, c: &[Point]) -> bool {
-    c.iter().any(|q| is_near(p, q))
-}
-
 /// Cluster a group of points into constellations that are each no more
 /// than NEAR Manhattan distance from at least one other group in the
 /// constellation.
+///
+/// Constellations are in the order of their first point.
 fn constellations(pts: &[Point]) -> Vec<Vec<Point>> {
-    let mut cts: Vec<Vec<Point>> = Vec::new();
-    for p in pts {
-        // There are three basic possibilities:
-        //
-        // 1. It's not near any already-known constellations: this is trivially
-        // true for the first point we look at since there are none. We make a new
-        // constellation.
-        //
-        // 2. It's near at least one point from only one existing constellation.
-        // We add it to that constellation and we're done.
-        //
-        // 3. It's near more than one existing constellation: we add it to the
-        // first and then fuse together all the others that we find.
-
-        // First, find the indexes of existing constellations which are near this point.
-        let ii: Vec<usize> = cts
-            .iter()
-            .enumerate()
-            .filter(|(_i, c)| near_constellation(p, c))
-            .map(|(i, _c)| i)
-            .collect();
-
-        if ii.is_empty() {
-            cts.push(vec![*p]);
-        } else {
-            cts[ii[0]].push(*p);
-            for j in ii[1..].iter().rev() {
-                // Consume them in reverse order so that the indexes don't change until
-                // we get to them.
-                let oc = cts.remove(*j);
-                cts[ii[0]].extend_from_slice(&oc);
+    let mut cts = UnionFind::new(pts.len());
+    for (i, p) in pts.iter().enumerate() {
+        for (j, q) in pts.iter().enumerate().skip(i + 1) {
+            if is_near(p, q) {
+                cts.union(i, j);
             }
         }
     }
-    cts
+    cts.components()
+        .into_iter()
+        .map(|c| c.into_iter().map(|i| pts[i]).collect())
+        .collect()
 }
 
 fn solve_a() -> usize {
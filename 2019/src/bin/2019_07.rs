@@ -14,7 +14,7 @@
 
 use std::convert::TryInto;
 
-use aoclib::permute::permutations;
+use aoclib::permute::heap_permutations;
 use mbpaoc2019::intcode::{parse_string, Computer};
 
 pub fn main() {
@@ -31,14 +31,14 @@ fn solve_type_a(prog: &str) -> (isize, Vec<usize>) {
     let mut best_pow = 0;
     let mut best_phases = Vec::new();
 
-    for phases in permutations(0..5) {
-        let pow = run_pipeline(&phases, &prog);
-        // println!("{:?} => {}", &phases, pow);
+    let mut phases: Vec<usize> = (0..5).collect();
+    heap_permutations(&mut phases, |phases| {
+        let pow = run_pipeline(phases, &prog);
         if pow > best_pow {
             best_pow = pow;
-            best_phases = phases.clone();
+            best_phases = phases.to_vec();
         }
-    }
+    });
     (best_pow, best_phases)
 }
 
@@ -51,14 +51,14 @@ fn solve_type_b(prog: &str) -> (isize, Vec<usize>) {
     let mut best_pow = 0;
     let mut best_phases = Vec::new();
 
-    for phases in permutations(5..=9) {
-        let pow = run_feedback(&phases, &prog);
-        // println!("{:?} => {}", &phases, pow);
+    let mut phases: Vec<usize> = (5..=9).collect();
+    heap_permutations(&mut phases, |phases| {
+        let pow = run_feedback(phases, &prog);
         if pow > best_pow {
             best_pow = pow;
-            best_phases = phases.clone();
+            best_phases = phases.to_vec();
         }
-    }
+    });
     (best_pow, best_phases)
 }
 
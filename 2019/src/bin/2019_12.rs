@@ -12,11 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-extern crate num_integer;
-
 use std::fmt;
 
-use num_integer::Integer;
+use aoclib::numtheory::lcm_all;
 
 use mbpaoc2019::ordering_to_int;
 
@@ -65,7 +63,7 @@ fn solve_b() -> usize {
         }
     }
     // dbg!(cycles);
-    cycles.iter().fold(1usize, |a, b| a.lcm(&b.unwrap()))
+    lcm_all(cycles.iter().map(|c| c.unwrap()))
 }
 
 impl Moon {
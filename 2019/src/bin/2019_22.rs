@@ -14,8 +14,7 @@
 
 use std::str::FromStr;
 
-use modinverse::modinverse;
-use num_integer::gcd;
+use aoclib::{Affine, DynModInt, Modular};
 
 // 22b: The number of cards, and the number of iterations, are both clearly so
 // high that we can't do them by brute force.
@@ -52,8 +51,8 @@ use num_integer::gcd;
 // (Obviously they will have factors, as they're the product of some of the input
 // values.)
 
-const B_CARDS: i128 = 119315717514047;
-const B_ROUNDS: i128 = 101741582076661;
+const B_CARDS: u64 = 119315717514047;
+const B_ROUNDS: u128 = 101741582076661;
 
 pub fn main() {
     println!("22a: {}", solve_a());
@@ -64,13 +63,13 @@ fn load_input() -> String {
     std::fs::read_to_string("input/input22.txt").unwrap()
 }
 
-fn solve_a() -> i128 {
+fn solve_a() -> u64 {
     let transforms = parse_input(&load_input());
     let collapsed = Fold::new(&transforms, 10007);
     collapsed.position_of_card(2019)
 }
 
-fn solve_b() -> i128 {
+fn solve_b() -> u64 {
     let transforms = parse_input(&load_input());
     let fold = Fold::new(&transforms, B_CARDS);
     let bigfold = fold.exponent(B_ROUNDS);
@@ -119,114 +118,72 @@ fn parse_input(s: &str) -> Vec<Transform> {
 }
 
 /// Transforms applied to the original deck so that card `i` ends up in position
-/// `(a * i + b).rem_euclid(n)`,
+/// `(a * i + b) mod n`.
 #[derive(Debug, Eq, PartialEq, Clone)]
 struct Fold {
-    a: i128,
-    b: i128,
-    n: i128,
+    f: Affine<DynModInt>,
 }
 
 impl Fold {
     /// Given a list of transforms, collapse it into a single linear transform
     /// `ax + b` describing the final position of card `x.`.
-    fn new(transforms: &[Transform], n: i128) -> Fold {
-        let mut a: i128 = 1;
-        let mut b: i128 = 0;
-        for t in transforms {
-            match t {
-                Reverse => {
-                    a = -a;
-                    b = -b - 1;
-                }
-                Multiply(i) => {
-                    a *= i;
-                    b *= i
-                }
-                Add(i) => {
-                    b -= *i;
-                }
-            }
-            a = a.rem_euclid(n);
-            b = b.rem_euclid(n);
-        }
-        Fold { a, b, n }
+    fn new(transforms: &[Transform], n: u64) -> Fold {
+        let m = |x: i128| DynModInt::new(x, n);
+        let f = transforms
+            .iter()
+            .map(|t| match t {
+                Reverse => Affine::new(m(-1), m(-1)),
+                Multiply(i) => Affine::new(m(*i), m(0)),
+                Add(i) => Affine::new(m(1), m(-i)),
+            })
+            .fold(Affine::new(m(1), m(0)), |f, g| f.compose(&g));
+        Fold { f }
     }
 
-    fn identity(n: i128) -> Fold {
-        Fold { a: 1, b: 0, n }
+    fn n(&self) -> u64 {
+        self.f.a.modulus()
     }
 
     /// Given a collapsed transform, produce the deck
     #[cfg(test)]
-    fn to_deck(&self) -> Vec<i128> {
-        let mut r = vec![-1; self.n as usize];
-        for i in 0..(self.n) {
-            let pos = self.position_of_card(i);
-            let pos: usize = pos.try_into().unwrap();
-            assert!(r[pos] == -1);
-            r[pos] = i;
+    fn to_deck(&self) -> Vec<u64> {
+        let mut r = vec![None; self.n() as usize];
+        for i in 0..self.n() {
+            let pos = self.position_of_card(i) as usize;
+            assert!(r[pos].is_none());
+            r[pos] = Some(i);
         }
-        assert!(!r.iter().any(|i| *i < 0));
-        r
+        r.into_iter().map(Option::unwrap).collect()
     }
 
-    fn position_of_card(&self, card: i128) -> i128 {
-        (self.a * card + self.b).rem_euclid(self.n)
+    fn position_of_card(&self, card: u64) -> u64 {
+        self.f.apply(self.f.a.with_value(card as i128)).value()
     }
 
     /// Given a collapsed transform, what card is in a given position?
-    fn card_in_position(&self, pos: i128) -> i128 {
-        assert!(pos >= 0);
-        assert!(pos < self.n);
-        // dbg!(pos, self);
-        let x = (pos - self.b).rem_euclid(self.n);
-        assert_eq!(gcd(self.a, self.n), 1);
-        // dbg!(gcd(self.a, self.n));
-        // dbg!(self.a / self.n, self.a % self.n);
-        debug_assert_eq!(self.a, self.a.rem_euclid(self.n));
-        let inv = modinverse(self.a, self.n).expect("no modular inverse");
-        // Now we need to find: what number, multiplied by a, equals pos, modulo self.n?
-        (inv * x).rem_euclid(self.n)
+    fn card_in_position(&self, pos: u64) -> u64 {
+        assert!(pos < self.n());
+        let inverse = self.f.inverse().expect("no modular inverse");
+        inverse.apply(self.f.a.with_value(pos as i128)).value()
     }
 
     // Calculate the fold that would result from applying this twice
+    #[cfg(test)]
     fn double(&self) -> Fold {
-        // pos = (a * c + b)
-        // pos^2 = (a * (a * c + b) + b)
-        // === a^2*c + ab + b
-        Fold {
-            a: (self.a * self.a).rem_euclid(self.n),
-            b: ((self.a + 1) * self.b).rem_euclid(self.n),
-            n: self.n,
-        }
+        self.multiply(self)
     }
 
     // Calculate the fold that results from applying two folds successively.
+    #[cfg(test)]
     fn multiply(&self, other: &Fold) -> Fold {
-        // pos = (a1 * (a2 * c + b2) + b1)
-        // === a1*a2*c + a1*b2 + b1
-        assert_eq!(self.n, other.n);
         Fold {
-            a: (self.a * other.a).rem_euclid(self.n),
-            b: (self.a * other.b + self.b).rem_euclid(self.n),
-            n: self.n,
+            f: self.f.compose(&other.f),
         }
     }
 
     // Raise this fold to an exponent.
-    fn exponent(&self, mut exp: i128) -> Fold {
-        let mut result = Fold::identity(self.n);
-        assert!(exp > 0);
-        let mut base = self.clone();
-        while exp > 0 {
-            if (exp & 1) == 1 {
-                result = result.multiply(&base);
-            }
-            base = base.double();
-            exp >>= 1;
-        }
-        result
+    fn exponent(&self, exp: u128) -> Fold {
+        Fold { f: self.f.pow(exp) }
     }
 }
 
@@ -234,12 +191,12 @@ impl Fold {
 mod test {
     use super::*;
 
-    fn cards_to_string(cards: &[i128]) -> String {
+    fn cards_to_string(cards: &[u64]) -> String {
         let s: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
         s.join(" ")
     }
 
-    fn check_eval(input: &str, n_cards: i128, expected: &str) {
+    fn check_eval(input: &str, n_cards: u64, expected: &str) {
         let transforms = parse_input(input);
         let collapse = Fold::new(&transforms, n_cards);
         let result = cards_to_string(&collapse.to_deck());
@@ -14,6 +14,8 @@
 
 //! Solve https://adventofcode.com/2020/day/13.
 
+use aoclib::numtheory::crt;
+
 pub fn main() {
     println!("13a: {}", solve_a());
     println!("13b: {}", solve_b());
@@ -52,29 +54,13 @@ fn solve_b() -> isize {
 }
 
 fn solve_type_b(inp: &str) -> isize {
-    let mut gaps = Vec::new();
-    let mut cycles: Vec<isize> = Vec::new();
-    for (i, s) in inp.split(',').enumerate().filter(|(_i, s)| *s != "x") {
-        gaps.push(i as isize);
-        cycles.push(s.parse().unwrap());
-    }
-    let mut ans = 0;
-    // This assumes all the cycles are all coprime (which they are in the input)
-    // -- in fact, they are all simply prime, a big clue.
-    // Any future coincidence must therefore be on a multiple of the product of
-    // all the cycles considered so far.
-    let mut step = 1;
-    for (i, c) in cycles.iter().enumerate() {
-        loop {
-            if (ans + gaps[i]) % c == 0 {
-                // println!("ans={} for i={}, c={}", ans, i, c);
-                step *= c;
-                break;
-            }
-            ans += step;
-        }
-    }
-    ans
+    // Bus `c` leaving `i` minutes after `t` means `t ≡ -i (mod c)`.
+    let congruences = inp
+        .split(',')
+        .enumerate()
+        .filter(|(_i, s)| *s != "x")
+        .map(|(i, s)| (-(i as isize), s.parse().unwrap()));
+    crt(congruences).expect("no time satisfies all the buses").0
 }
 
 #[cfg(test)]
@@ -16,6 +16,7 @@
 
 use std::collections::HashSet;
 
+use aoclib::graph::{Graph, NodeId};
 use aoclib::*;
 
 pub fn main() {
@@ -119,44 +120,36 @@ fn solve_b() -> usize {
     }
     // dbg!(valid_tickets);
 
-    // What possible meaning could each column have?
+    // What possible meaning could each column have? Join each column to every
+    // field name it could be, and then match columns to distinct names.
     let n_fields = valid_tickets[0].len();
-    let mut narrowing: Vec<HashSet<String>> = Vec::new();
-    for field_idx in 0..n_fields {
+    let mut possible = Graph::<(), ()>::undirected();
+    let columns: Vec<NodeId> = (0..n_fields)
+        .map(|i| possible.add_node(&format!("column {i}"), ()))
+        .collect();
+    for (field_idx, &column) in columns.iter().enumerate() {
         if let Some(poss) = valid_tickets
             .iter()
             .map(|t| t[field_idx])
             .map(|f| possible_fields(f, &defns))
             .reduce(|x, y| x.intersection(&y).cloned().collect())
         {
-            // println!("{} => {:#?}", field_idx, poss);
-            narrowing.push(poss);
-        }
-    }
-
-    // Find one column with an unambiguous meaning; assign that; remove it as a possibility
-    // for others, and iterate until everything is resolved.
-    let mut known: Vec<Option<String>> = vec![None; n_fields];
-    for _i in 0..n_fields {
-        // find the one value that occurs once
-        let (found_idx, found_set) = narrowing
-            .iter()
-            .enumerate()
-            .find(|(_idx, hs)| hs.len() == 1)
-            .unwrap();
-        let found_name = found_set.iter().next().unwrap().clone();
-        // println!("decided {} = {}", found_idx, found_name);
-        known[found_idx] = Some(found_name.clone());
-        for hs in narrowing.iter_mut() {
-            hs.remove(&found_name);
+            for name in poss {
+                let name = possible.node(&name);
+                possible.add_edge(column, name, ());
+            }
         }
     }
+    let matching = possible.maximum_matching(&columns);
+    assert_eq!(matching.len(), n_fields, "not every column has a meaning");
 
-    known
+    matching
         .iter()
-        .enumerate()
-        .filter(|(_, s)| s.as_ref().unwrap().starts_with("departure"))
-        .map(|(idx, _s)| mytick[idx])
+        .filter(|(_column, name)| possible.name(*name).starts_with("departure"))
+        .map(|(column, _name)| {
+            let idx = columns.iter().position(|c| c == column).unwrap();
+            mytick[idx]
+        })
         .product()
 }
 
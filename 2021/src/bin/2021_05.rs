@@ -2,6 +2,7 @@
 
 //! https://adventofcode.com/2021/day/5
 
+use aoclib::line::Segment;
 use aoclib::{Matrix, Point};
 
 fn main() {
@@ -34,18 +35,12 @@ fn solve(input: &str, include_diagonals: bool) -> usize {
     let max_y = lines.iter().flat_map(|(a, b)| [a.y, b.y]).max().unwrap();
     let mut mat = Matrix::new(max_x as usize + 1, max_y as usize + 1, 0usize);
     for (a, b) in lines {
-        let xs = (b.x - a.x).signum();
-        let ys = (b.y - a.y).signum();
-        if !include_diagonals && (xs != 0) && (ys != 0) {
+        let segment = Segment::new(a, b);
+        if !include_diagonals && !segment.is_axis_aligned() {
             continue;
         }
-        let mut p = a;
-        loop {
+        for p in segment.points() {
             mat[p] += 1;
-            if p == b {
-                break;
-            }
-            p = p.delta(xs, ys);
         }
     }
     mat.values().filter(|c| **c > 1).count()
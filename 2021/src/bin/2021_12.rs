@@ -2,7 +2,7 @@
 
 //! https://adventofcode.com/2021/day/12
 
-use std::collections::BTreeMap;
+use aoclib::graph::{Graph, NodeId};
 
 fn main() {
     let input = input();
@@ -19,53 +19,32 @@ fn small(s: &str) -> bool {
 }
 
 fn solve(input: &str) -> (usize, usize) {
-    // Connections from room k. (Connections are bidirectional; entries are added in each
-    // direction.)
-    let mut from: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
-    input
-        .lines()
-        .map(|l| l.split_once('-').unwrap())
-        .for_each(|(a, b)| {
-            from.entry(a).or_default().push(b);
-            from.entry(b).or_default().push(a);
-        });
-
-    let mut inc: Vec<Vec<&str>> = vec![vec!["start"]];
-    let mut sol_a = 0;
-    while let Some(p) = inc.pop() {
-        for next in &from[p.last().unwrap()] {
-            if *next == "end" {
-                sol_a += 1;
-            } else if !p.contains(next) || !small(next) {
-                let mut q = p.clone();
-                q.push(next);
-                inc.push(q);
-            }
-        }
+    let mut caves = Graph::<(), ()>::undirected();
+    for (a, b) in input.lines().map(|l| l.split_once('-').unwrap()) {
+        caves.add_edge_named(a, b, ());
     }
+    let start = caves.find_node("start").unwrap();
+    let end = caves.find_node("end").unwrap();
+    let is_small = |n: NodeId| small(caves.name(n));
 
-    let mut inc: Vec<Vec<&str>> = vec![vec!["start"]];
-    let mut sol_b = 0;
-    while let Some(p) = inc.pop() {
-        for next in &from[p.last().unwrap()] {
-            if *next == "end" {
-                sol_b += 1;
-            } else if *next != "start" && !(small(next) && p.contains(next) && has_two_small(&p)) {
-                let mut q = p.clone();
-                q.push(next);
-                inc.push(q);
-            }
-        }
-    }
+    let sol_a = caves
+        .paths_with(start, end, |p, next| !is_small(next) || !p.contains(&next))
+        .count();
+
+    let sol_b = caves
+        .paths_with(start, end, |p, next| {
+            next != start && !(is_small(next) && p.contains(&next) && has_two_small(&caves, p))
+        })
+        .count();
 
     (sol_a, sol_b)
 }
 
 /// True if there is already any small room occurring twice
-fn has_two_small(p: &[&str]) -> bool {
+fn has_two_small(caves: &Graph<(), ()>, p: &[NodeId]) -> bool {
     p.iter()
         .enumerate()
-        .filter(|(_, w)| small(w))
+        .filter(|(_, w)| small(caves.name(**w)))
         .any(|(i, w)| p.iter().skip(i + 1).any(|x| *x == *w))
 }
 
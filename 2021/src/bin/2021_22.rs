@@ -2,13 +2,7 @@
 
 //! https://adventofcode.com/2021/day/22 "Reactor Reboot"
 
-// TODO: There is a better way to do this, that doesn't require keeping
-// track of such large data volumes.
-//
-// Something like: keep track of the volume of each cuboid turned on;
-// subtract the intersecting volume.
-
-use ndarray::prelude::*;
+use aoclib::{Cuboid, CuboidSet};
 
 fn main() {
     let (a, b) = solve(&input());
@@ -25,8 +19,10 @@ fn solve(input: &str) -> (usize, u64) {
         r"(on|off) x=(-?\d+)\.\.(-?\d+),y=(-?\d+)\.\.(-?\d+),z=(-?\d+)\.\.(-?\d+)",
     )
     .unwrap();
-    let mut cubes: Array3<bool> = Array3::default((101, 101, 101));
-    let mut insts: Vec<(bool, [isize; 6])> = Vec::new();
+    // Keep track of the lit cubes as a set of disjoint boxes: each
+    // instruction cuts away whatever it overlaps, and an `on` instruction
+    // then adds its own box.
+    let mut lit: CuboidSet<3> = CuboidSet::new();
     for l in input.lines() {
         let caps = re.captures(l).unwrap();
         let on = caps[1] == *"on";
@@ -36,105 +32,18 @@ fn solve(input: &str) -> (usize, u64) {
             .map(|p| p.unwrap().as_str().parse::<isize>().unwrap())
             .collect();
         if let [x0, x1, y0, y1, z0, z1] = v[..6] {
-            insts.push((on, [x0, x1, y0, y1, z0, z1]));
-            if x0 < -50 || x1 > 50 || y0 < -50 || y1 > 50 || z0 < -50 || z1 > 50 {
-                continue;
-            }
-            dbg!(on, &v);
-            for x in x0..=x1 {
-                for y in y0..=y1 {
-                    for z in z0..=z1 {
-                        cubes[[(x + 50) as usize, (y + 50) as usize, (z + 50) as usize]] = on;
-                    }
-                }
-            }
-        }
-    }
-    let sol_a = cubes.iter().filter(|x| **x).count();
-
-    // So we only need to worry about the coordinates on each axis that
-    // are actually mentioned in the input: the values can never vary in
-    // between.
-    //
-    // Let's make a map from each x, y, z value to a simpler integer index
-    // of the unique values on that axis. We can then make a simpler cuboid
-    // with one-bit cells between each of those values. The volume of each
-    // cuboid depends on its real coordinates. We can just walk them to
-    // collect the total volume.
-    //
-    // (It's still kinda big at 580M elements.)
-    //
-    // Probably something more compact might be possible with a binary
-    // space-partitioning tree? But it could be more complex to
-    // generate.
-
-    // Collect the unique x values.
-    let mut xvals: Vec<isize> = Vec::new();
-    let mut yvals = Vec::new();
-    let mut zvals = Vec::new();
-    for (_on, [x0, x1, y0, y1, z0, z1]) in &insts {
-        xvals.push(*x0);
-        xvals.push(*x1 + 1);
-        yvals.push(*y0);
-        yvals.push(*y1 + 1);
-        zvals.push(*z0);
-        zvals.push(*z1 + 1);
-    }
-    xvals.sort_unstable();
-    xvals.dedup();
-    yvals.sort_unstable();
-    yvals.dedup();
-    zvals.sort_unstable();
-    zvals.dedup();
-    dbg!(xvals.len(), yvals.len(), zvals.len());
-
-    // lit[xi,yi,zi] == true means that the voxel of xvals[xi]..xvals[xi+1] etc
-    // is lit.
-    //
-    // Ranges are inclusive so the natural representation is also
-    // inclusive. But we need to be able to handle the case where an off
-    // overlaps by one with an `on` and so it turns off one column.
-    //
-    // So we'll track them as semi-open, and convert to that form when
-    // generating the array.
-    let mut lit: Array3<bool> = Array3::default((xvals.len(), yvals.len(), zvals.len()));
-    dbg!(lit.len());
-    for (on, [x0, x1, y0, y1, z0, z1]) in insts {
-        let xi0 = idx(x0, &xvals);
-        let xi1 = idx(x1 + 1, &xvals);
-        let yi0 = idx(y0, &yvals);
-        let yi1 = idx(y1 + 1, &yvals);
-        let zi0 = idx(z0, &zvals);
-        let zi1 = idx(z1 + 1, &zvals);
-        for i in xi0..xi1 {
-            for j in yi0..yi1 {
-                for k in zi0..zi1 {
-                    lit[[i, j, k]] = on;
-                }
+            // Ranges in the input are inclusive.
+            let c = Cuboid::from_inclusive([x0, y0, z0], [x1, y1, z1]);
+            if on {
+                lit.insert(c);
+            } else {
+                lit.remove(&c);
             }
         }
     }
-    println!("built lit");
-
-    let xsz: Vec<isize> = xvals.windows(2).map(|w| w[1] - w[0]).collect();
-    let ysz: Vec<isize> = yvals.windows(2).map(|w| w[1] - w[0]).collect();
-    let zsz: Vec<isize> = zvals.windows(2).map(|w| w[1] - w[0]).collect();
-
-    let mut sol_b: u64 = 0;
-    let mut cells_set = 0;
-    for ((xi, yi, zi), &on) in lit.indexed_iter() {
-        if on {
-            sol_b += (xsz[xi] * ysz[yi] * zsz[zi]) as u64;
-            cells_set += 1;
-        }
-    }
-    dbg!(cells_set);
-
-    (sol_a, sol_b)
-}
-
-fn idx(x: isize, vals: &[isize]) -> usize {
-    vals.binary_search(&x).unwrap()
+    let init_region = Cuboid::from_inclusive([-50; 3], [50; 3]);
+    let sol_a = lit.clipped(&init_region).volume() as usize;
+    (sol_a, lit.volume())
 }
 
 #[cfg(test)]
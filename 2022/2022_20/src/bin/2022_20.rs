@@ -2,7 +2,7 @@
 
 use itertools::Itertools;
 
-use mbpaoc2022_20::modint::{add_isize_mod, add_usize_mod, sub_usize_mod};
+use aoclib::{DynModInt, Modular};
 
 #[allow(dead_code)]
 static EX: &str = "\
@@ -19,6 +19,11 @@ static INPUT: &str = include_str!("../../input.txt");
 
 static KEY: isize = 811589153;
 
+/// Position `p` in a ring of `l` elements.
+fn ring(p: usize, l: usize) -> DynModInt {
+    DynModInt::new(p as i128, l as u64)
+}
+
 fn parse(input: &str) -> Vec<isize> {
     input
         .lines()
@@ -123,18 +128,21 @@ impl Perm {
         let y = self.input_pos[x];
         // Skipping just (ll-1) elements would result in no change.
         let s = s % (ll - 1);
-        let z = add_isize_mod(y, s, l);
+        let z = (ring(y, l) + s as i64).value() as usize;
+        // The loops below move one position at a time, so they wrap around
+        // by hand rather than building a DynModInt for every step.
+        let mut ii = y;
         #[allow(clippy::comparison_chain)]
         if s > 0 {
             // The element whose output position is currently y changes to output
             // z, z > y. Every element in (y+1)..=z is reduced by one to make
             // room.
-            for i in 1..=(s) {
-                let ii = add_isize_mod(y, i, l);
+            for _ in 1..=(s) {
+                // this input now goes to an output 1 position left
+                let q = ii;
+                ii = if ii + 1 == l { 0 } else { ii + 1 };
                 // which input goes to this output?
                 let r = self.pos_input[ii];
-                // this input now goes to an output 1 position left
-                let q = sub_usize_mod(ii, 1, l);
                 self.input_pos[r] = q;
                 // the new output comes from this input.
                 self.pos_input[q] = r;
@@ -143,12 +151,12 @@ impl Perm {
             // Move the prior s elements (with wrapping) right by one, then
             // move x to z.
             let s = -s as usize;
-            for i in 1..=(s) {
-                let ii = sub_usize_mod(y, i, l);
+            for _ in 1..=(s) {
+                // this input now goes to an output 1 position right
+                let q = ii;
+                ii = if ii == 0 { l - 1 } else { ii - 1 };
                 // which input goes to this output?
                 let r = self.pos_input[ii];
-                // this input now goes to an output 1 position right
-                let q = add_usize_mod(ii, 1, l);
                 self.input_pos[r] = q;
                 // the new output comes from this input.
                 self.pos_input[q] = r;
@@ -181,11 +189,11 @@ impl Perm {
         if l <= 1 {
             return true;
         }
-        let d = sub_usize_mod(other.input_pos[0], self.input_pos[0], l);
+        let d = ring(other.input_pos[0], l) - ring(self.input_pos[0], l);
         self.input_pos
             .iter()
             .zip(&other.input_pos)
-            .all(|(a, b)| sub_usize_mod(*b, *a, l) == d)
+            .all(|(a, b)| ring(*b, l) - ring(*a, l) == d)
     }
 }
 
@@ -215,7 +223,7 @@ fn grove_coord(perm: &Perm, input: &[isize]) -> isize {
     let zero_pos = applied.iter().position(|i| *i == 0).unwrap();
     [1000, 2000, 3000]
         .iter()
-        .map(|i| applied[add_usize_mod(zero_pos, *i, l)])
+        .map(|i| applied[(ring(zero_pos, l) + *i).value() as usize])
         .sum()
 }
 
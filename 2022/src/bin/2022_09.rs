@@ -43,11 +43,9 @@ fn solve(input: &str, rope_len: usize) -> usize {
             // because of reasons in
             // <https://users.rust-lang.org/t/iterator-over-mutable-windows-of-slice/17110/4>.
             for i in 0..(rope_len - 1) {
-                let dx = knots[i].x - knots[i + 1].x;
-                let dy = knots[i].y - knots[i + 1].y;
-                if dx.abs() > 1 || dy.abs() > 1 {
-                    knots[i + 1].x += dx.signum();
-                    knots[i + 1].y += dy.signum();
+                if knots[i].chebyshev_distance(&knots[i + 1]) > 1 {
+                    let pull = (knots[i] - knots[i + 1]).signum();
+                    knots[i + 1] += pull;
                 }
             }
             vis[knots[rope_len - 1]] = true;
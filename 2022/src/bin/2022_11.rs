@@ -2,6 +2,7 @@
 
 use std::collections::VecDeque;
 
+use aoclib::TopK;
 use itertools::Itertools;
 
 fn main() {
@@ -135,13 +136,9 @@ fn solve_b(input: &str) -> usize {
 }
 
 fn monkey_business(monkeys: &[Monkey]) -> usize {
-    monkeys
-        .iter()
-        .map(|m| m.inspected)
-        .sorted()
-        .rev()
-        .take(2)
-        .product()
+    let mut top = TopK::new(2);
+    top.extend(monkeys.iter().map(|m| m.inspected));
+    top.into_sorted_vec().into_iter().product()
 }
 
 #[cfg(test)]
@@ -34,12 +34,10 @@ fn solve_a(input: &str) -> usize {
 fn solve_b(input: &str) -> usize {
     let map = Matrix::from_string_lines(input);
     let end = map.find_single_value(&'E');
-    map.find_values(&'S')
-        .chain(map.find_values(&'a'))
-        .flat_map(|start| map.shortest_path(start, end, can_move))
-        .map(|path| path.distance())
-        .min()
+    let starts = map.find_values(&'S').chain(map.find_values(&'a'));
+    map.shortest_path_from_any(starts, end, can_move)
         .expect("no paths found")
+        .distance()
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 //! https://adventofcode.com/2022/day/14
 
-use aoclib::{line_between, point, Matrix, Point};
+use aoclib::line::polyline;
+use aoclib::{point, Matrix, Point};
 
 static TAP: Point = point(500, 0);
 
@@ -20,10 +21,8 @@ fn load(input: &str) -> Matrix<char> {
         .collect();
     let mut mat = Matrix::new(1200, 600, '.'); // empirical/guessed size
     for shape in draw {
-        for pair in shape.windows(2) {
-            for pq in line_between(pair[0], pair[1]) {
-                mat[pq] = '#'
-            }
+        for pq in polyline(&shape) {
+            mat[pq] = '#'
         }
     }
     mat
@@ -2,6 +2,7 @@
 
 use std::collections::BTreeSet;
 
+use aoclib::range::IntervalSet;
 use aoclib::{point, Point};
 use itertools::Itertools;
 use regex::Regex;
@@ -26,39 +27,21 @@ fn solve_a(input: &str, tgt: isize) -> usize {
         assert_eq!(c.len(), 4);
         sbs.push((point(c[0], c[1]), point(c[2], c[3])));
     }
-    // dbg!(&sbs);
-    let mut not: BTreeSet<isize> = Default::default();
-    // points that are not a beacon
-    // and no further from a sensor than its beacon.
-    // let mut maxcnt = 0;
+    // Points that are not a beacon and no further from a sensor than its beacon.
+    let mut not: IntervalSet<isize> = IntervalSet::new();
     for (s, b) in &sbs {
         let rad = (s.x - b.x).abs() + (s.y - b.y).abs();
-        let dy = (s.y - tgt).abs();
-        let mx = rad - dy;
-        if mx < 0 {
-            continue;
-        }
-        // dbg!(s, b, rad, dy, mx);
-        let mut cnt = 0;
-        for ix in (s.x - mx)..=(s.x + mx) {
-            if not.insert(ix) {
-                // maxcnt += 1
-            };
-            cnt += 1;
-            // maxcnt += 1;
+        let mx = rad - (s.y - tgt).abs();
+        if mx >= 0 {
+            not.insert((s.x - mx)..(s.x + mx + 1));
         }
-        assert_eq!(cnt, 2 * mx + 1);
-        // dbg!(not.len());
     }
     for (_, b) in &sbs {
         if b.y == tgt {
-            not.remove(&b.x);
-            // dbg!(&b);
+            not.remove(b.x..(b.x + 1));
         }
     }
-    // it's not 4704090
-    // dbg!(&maxcnt);
-    not.len()
+    not.total_len() as usize
 }
 
 fn solve_b(input: &str, maxco: isize) -> isize {
@@ -1,7 +1,9 @@
 //! https://adventofcode.com/2022/day/17
 
 use std::fmt;
+use std::rc::Rc;
 
+use aoclib::cycle::find_cycle;
 use aoclib::Matrix;
 
 const MAP_WIDTH: usize = 7;
@@ -246,83 +248,29 @@ fn solve_a(input: &str, rounds: usize) -> usize {
 }
 
 fn solve_b(input: &str, rounds: usize) -> usize {
-    let mut game = Game::new(input);
-    let mut rrs = Vec::new();
-    let mut initial_rounds = None; // Number of rounds before the cycle is seen to start.
-    let mut cycle_rounds = None; // Number of rounds in each cycle.
-    let mut cycle_hits = 0;
-    for i_round in 0.. {
-        let rr = game.drop_next();
-        // println!("{} {}", rr.i_rock, rr.i_move);
-        // println!("{}", rr.map.to_string());
-        // assert_eq!(rr.i_round, i_round);
-        if let Some(x) = rrs.iter().rposition(|x| *x == rr) {
-            // If the previous rr matches this, then the length would be 1, not 0.
-            // println!(
-            //     "repeat? cycle of length {} found at i_round={i_round} returns to round={x} move={} rock={} moves{}",
-            //     i_round - x,
-            //     rr.i_move,
-            //     rr.i_rock,
-            //     rr.moves
-            // );
-            if initial_rounds.is_none() {
-                // Cycle starts on the first occurrence of rr, initial rounds is the number of
-                // rounds prior to that.
-                initial_rounds = Some(x);
-            }
-            cycle_hits += 1;
-            match cycle_rounds {
-                None => {
-                    cycle_rounds = Some(i_round - x);
-                }
-                Some(rounds) => {
-                    assert_eq!(rounds, i_round - x, "cycle length is not stable");
-                }
-            }
-            // println!("{}", rr.map.to_string());
-            // println!("previous moves\n{}", rrs[x].map.to_string());
-            if cycle_hits > 20 {
-                break; // Confident enough that now it continues; really once would be enough.
-            }
-        }
-        rrs.push(rr);
-    }
-    // So there are three parts to the overall many rounds: some initial rounds, some repetition of
-    // cycles, and then a final incomplete cycle.
-    let cycle_rounds = cycle_rounds.unwrap();
-    let initial_rounds = initial_rounds.unwrap();
-    let initial_growth = rrs
-        .iter()
-        .take(initial_rounds)
-        .map(|rr| rr.growth)
-        .sum::<usize>();
-    let n_cycles = (rounds - initial_rounds) / cycle_rounds;
-    let cycle_growth = rrs
-        .iter()
-        .skip(initial_rounds)
-        .take(cycle_rounds)
-        .map(|rr| rr.growth)
-        .sum::<usize>();
-    let tail_rounds = rounds - initial_rounds - n_cycles * cycle_rounds;
-    let tail_growth = rrs
-        .iter()
-        .skip(initial_rounds)
-        .take(tail_rounds)
-        .map(|rr| rr.growth)
-        .sum::<usize>();
-    // dbg!(
-    //     initial_rounds,
-    //     initial_growth,
-    //     cycle_rounds,
-    //     n_cycles,
-    //     cycle_growth,
-    //     tail_rounds,
-    //     tail_growth
-    // );
-    initial_growth + n_cycles * cycle_growth + tail_growth
+    // After some initial rounds the game falls into a cycle, where the same rock
+    // lands at the same point in the moves on the same shape of map, and the
+    // tower grows by the same height every time around the cycle.
+    let history = find_cycle(
+        Game::new(input),
+        |game| {
+            let mut game = game.clone();
+            game.drop_next();
+            game
+        },
+        |game| {
+            (
+                game.i_rock,
+                game.i_move,
+                game.base_height == 0,
+                game.map.clone(),
+            )
+        },
+    );
+    history.extrapolate(rounds, |game| game.tower_height as i64) as usize
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 struct Map {
     /// The content of each column, where element 0 is at the bottom, and
     /// true values are occupied. All cols must be the same length (i.e. height).
@@ -436,40 +384,28 @@ impl fmt::Display for Map {
     }
 }
 
+#[derive(Clone)]
 struct Game {
     /// Next rock to be played
     i_rock: usize,
     i_move: usize,
     i_round: usize,
-    rocks: Vec<Rock>,
-    moves: Vec<char>,
+    rocks: Rc<Vec<Rock>>,
+    moves: Rc<Vec<char>>,
     map: Map,
     tower_height: usize,
     /// Height above the ground of the bottom of the map.
     base_height: usize,
 }
 
-#[derive(PartialEq, Eq)]
-struct RoundResult {
-    /// How much did the height of the tower increase?
-    growth: usize,
-
-    /// Which rock was played?
-    i_rock: usize,
-    i_move: usize,
-
-    /// What does the reachable map look like after this round?
-    map: Map,
-}
-
 impl Game {
     fn new(input: &str) -> Game {
         Game {
             i_rock: 0,
             i_move: 0,
             i_round: 1, // 1-based
-            rocks: rocks(),
-            moves: input.trim().chars().collect(),
+            rocks: Rc::new(rocks()),
+            moves: Rc::new(input.trim().chars().collect()),
             map: Map::new(),
             tower_height: 0,
             base_height: 0,
@@ -478,8 +414,8 @@ impl Game {
 
     /// Drop one rock; consume however many moves it takes for it to settle.
     ///
-    /// Updates the game state and returns info about how the move turned out.
-    fn drop_next(&mut self) -> RoundResult {
+    /// Updates the game state, including the height of the tower.
+    fn drop_next(&mut self) {
         // y is the position of the top of the rock, measured down from top of the map
         let rock = &self.rocks[self.i_rock];
         let mut y = self.map.max_block_height() + rock.height() + 2;
@@ -510,12 +446,6 @@ impl Game {
         // println!("{}\n", self.map.to_string());
         let growth = self.map.max_block_height() - orig_block_height;
         self.tower_height += growth;
-        let r = RoundResult {
-            i_rock: self.i_rock,
-            i_move: self.i_move,
-            growth,
-            map: self.map.clone(),
-        };
         self.i_rock = (self.i_rock + 1) % self.rocks.len();
         self.i_round += 1;
         let truncated = self.map.truncate();
@@ -527,7 +457,6 @@ impl Game {
             // );
         }
         self.base_height += truncated;
-        r
     }
 }
 
@@ -1,6 +1,9 @@
 //! https://adventofcode.com/2022/day/21
 
 use std::collections::HashMap;
+use std::ops::{Add, Div, Mul, Sub};
+
+use aoclib::{Memo, Rational};
 
 fn main() {
     // println!("{}", solve_a(EX));
@@ -22,64 +25,57 @@ impl<'a> Mk<'a> {
     fn eval(&self, vals: &HashMap<&'a str, isize>) -> isize {
         match self {
             Mk::Const(x) => *x,
-            Mk::Op(opch, an, bn) => {
-                let a = vals[an];
-                let b = vals[bn];
-                match opch {
-                    '+' => a + b,
-                    '-' => a - b,
-                    '*' => a * b,
-                    '/' => a / b,
-                    _ => panic!(),
-                }
-            }
+            Mk::Op(opch, an, bn) => apply(*opch, vals[an], vals[bn]),
         }
     }
 }
+
+fn apply<T>(opch: char, a: T, b: T) -> T
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    match opch {
+        '+' => a + b,
+        '-' => a - b,
+        '*' => a * b,
+        '/' => a / b,
+        _ => panic!(),
+    }
+}
+
 type Mkmap<'a> = HashMap<&'a str, Mk<'a>>;
 
+/// Remembers the value of every monkey that doesn't depend on humn.
+///
+/// Values are kept as exact fractions, so that solving for humn doesn't
+/// round anything off along the way.
+type Known<'a> = Memo<&'a str, Option<Rational>>;
+
 /// Evaluate a monkey if it can be done without relying on humn, for part 2.
-fn eval_maybe<'a>(
-    name: &'a str,
-    mks: &HashMap<&'a str, Mk<'a>>,
-    memo: &mut HashMap<&'a str, isize>,
-) -> Option<isize> {
-    let mk = &mks[name];
-    println!("attempt eval {name} {mk:?}");
-    if name == "humn" {
-        return None;
-    }
-    assert_ne!(name, "root");
-    let v = match mk {
-        Mk::Const(x) => Some(*x),
-        Mk::Op(opch, an, bn) => {
-            if let (Some(a), Some(b)) = (eval_maybe(an, mks, memo), eval_maybe(bn, mks, memo)) {
-                Some(match opch {
-                    '+' => a + b,
-                    '-' => a - b,
-                    '*' => a * b,
-                    '/' => a / b,
-                    _ => panic!(),
-                })
-            } else {
-                println!("evaluation of {name} stalled");
-                None
+fn eval_maybe<'a>(name: &'a str, mks: &Mkmap<'a>, memo: &mut Known<'a>) -> Option<Rational> {
+    memo.recurse(name, &|eval, name| {
+        if name == "humn" {
+            return None;
+        }
+        assert_ne!(name, "root");
+        match mks[name] {
+            Mk::Const(x) => Some(Rational::from(x)),
+            Mk::Op(opch, an, bn) => {
+                // Evaluate both sides, so that the known one is remembered
+                // for push_down.
+                let (a, b) = (eval(an), eval(bn));
+                Some(apply(opch, a?, b?))
             }
         }
-    };
-    if let Some(v) = v {
-        memo.insert(name, v);
-    }
-    println!("result {name} => {v:?}");
-    v
+    })
 }
 
-fn from_root(mks: &Mkmap) -> isize {
+fn from_root(mks: &Mkmap) -> Rational {
     let mk = &mks["root"];
     // one side will have a known value; one unknown
     let unn: &str;
-    let known: isize;
-    let mut memo: HashMap<&str, isize> = HashMap::new();
+    let known: Rational;
+    let mut memo = Known::new();
     match mk {
         Mk::Op(_, an, bn) => {
             let aval = eval_maybe(an, mks, &mut memo);
@@ -99,14 +95,14 @@ fn from_root(mks: &Mkmap) -> isize {
         _ => panic!(),
     }
     println!("root known value {known}, unknown side {unn}");
-    push_down(unn, mks, known, &mut memo)
+    push_down(unn, mks, known, &memo)
 }
 
 /// Given the known value of an expression push down towards the unknown human value and
 /// eventually return it.
 ///
 /// This should only be called for unknown values.
-fn push_down(name: &str, mks: &Mkmap, val: isize, memo: &mut HashMap<&str, isize>) -> isize {
+fn push_down(name: &str, mks: &Mkmap, val: Rational, memo: &Known) -> Rational {
     if name == "humn" {
         println!("found humn {val}");
         return val;
@@ -119,8 +115,8 @@ fn push_down(name: &str, mks: &Mkmap, val: isize, memo: &mut HashMap<&str, isize
         // yet.
         Mk::Const(_) => panic!(),
         Mk::Op(opch, an, bn) => {
-            let aval = memo.get(an);
-            let bval = memo.get(bn);
+            let aval = memo.get(an).copied().flatten();
+            let bval = memo.get(bn).copied().flatten();
             println!("a: {an}={aval:?}, b: {bn}={bval:?}");
             match (aval, bval) {
                 (Some(a), None) => {
@@ -128,7 +124,7 @@ fn push_down(name: &str, mks: &Mkmap, val: isize, memo: &mut HashMap<&str, isize
                         '+' => val - a, // val = a + b; b = val - a
                         '-' => a - val, // val = a - b; b = a - val
                         '*' => val / a, // val = a * b; b = val / a
-                        '/' => val / a, // val = a / b; b = a / val
+                        '/' => a / val, // val = a / b; b = a / val
                         _ => panic!(),
                     };
                     push_down(bn, mks, dn, memo)
@@ -211,7 +207,8 @@ fn solve_b(input: &str) -> isize {
 
     // eval_maybe("pppw", &mks);
     // eval_maybe("sjmn", &mks);
-    from_root(&mks)
+    let humn = from_root(&mks);
+    humn.to_integer().expect("humn is not a whole number") as isize
 }
 
 #[allow(dead_code)]
@@ -242,6 +239,11 @@ mod test {
         assert_eq!(solve_a(&input()), 169525884255464);
     }
 
+    #[test]
+    fn example_b() {
+        assert_eq!(solve_b(EX), 301);
+    }
+
     #[test]
     fn solution_b() {
         assert_eq!(solve_b(&input()), 3247317268284);
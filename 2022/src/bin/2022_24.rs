@@ -278,6 +278,10 @@ fn demo(input: &str) -> usize {
 }
 
 fn solve_b(input: &str) -> usize {
+    // Each leg starts from the single state where the previous one finished,
+    // so this is three searches from one origin each rather than one search
+    // from several. Arriving earliest is always best, because it's safe to
+    // wait at the start or end.
     let map = Map::parse(input);
     let path = find_path(&map, 0, Place::Start, Place::End);
     let clock = path.final_point().clock;
@@ -1,15 +1,13 @@
 //! https://adventofcode.com/2023/day/5
 
-use std::cmp::{max, min};
 use std::fs::read_to_string;
 
+use aoclib::range::{IntervalSet, RangeMap};
 use itertools::Itertools;
 
 static YEAR: &str = "2023";
 static DAY: &str = "05";
 
-type Range = std::ops::Range<u64>;
-
 fn main() {
     let input = &input();
     println!("{YEAR}_{DAY} a {}", solve_a(input));
@@ -23,42 +21,34 @@ fn input() -> String {
 }
 
 fn solve_a(input: &str) -> u64 {
-    let (mut a, phases) = parse(input);
-    for phases in &phases {
-        a = a.iter().map(|s| phases.lookup(*s)).collect_vec();
-    }
-    *a.iter().min().expect("found a location")
+    let (seeds, phases) = parse(input);
+    let almanac = compose(&phases);
+    seeds
+        .into_iter()
+        .map(|s| almanac.lookup(s))
+        .min()
+        .expect("found a location")
 }
 
 fn solve_b(input: &str) -> u64 {
-    /*
-    In the real data, the ranges are too large to probe every contained value
-    individually; instead we need a way to take something analogous to the
-    product or composition of two range maps.
-
-    This can be done one step at a time through the stages.
-
-    Given a list of input ranges, we produce a list of output ranges. For each
-    input range, match it against each span; typically this will split it into
-    up to three parts (before, matching, and after.) Anything that remains unmatched
-    against _any_ span keeps its original value.
-    */
-    // let (mut a, maps) = parse(input);
+    // The ranges are too large to probe every contained value individually,
+    // so instead collapse all the phases into one map, and push whole ranges
+    // of seeds through it.
     let (seeds, phases) = parse(input);
-    let mut ranges = seeds
+    let seeds: IntervalSet<u64> = seeds
         .into_iter()
         .tuples::<(u64, u64)>()
         .map(|(start, len)| start..(start + len))
-        .collect_vec();
-    // dbg!(&ranges);
-    for phase in &phases {
-        ranges = ranges
-            .into_iter()
-            .flat_map(|range| phase.map_range(range))
-            .collect();
-        // println!("After {}, ranges: {:#?}", phase.name, ranges);
-    }
-    ranges.into_iter().map(|r| r.start).min().unwrap()
+        .collect();
+    let locations = compose(&phases).apply(&seeds);
+    locations.span().expect("found a location").start
+}
+
+/// Combine all the phases into one map from seeds to locations.
+fn compose(phases: &[Phase]) -> RangeMap<u64> {
+    phases
+        .iter()
+        .fold(RangeMap::new(), |acc, phase| acc.compose(&phase.map))
 }
 
 fn parse(input: &str) -> (Vec<u64>, Vec<Phase>) {
@@ -75,8 +65,6 @@ fn parse(input: &str) -> (Vec<u64>, Vec<Phase>) {
     assert!(lines.next().expect("a second line").trim().is_empty());
     let mut phases = Vec::new();
     while let Some(phase) = Phase::from_lines(&mut lines) {
-        // println!("{}", map.name);
-        // println!("{:#?}", map.ranges);
         phases.push(phase)
     }
     (start_seeds, phases)
@@ -86,7 +74,7 @@ fn parse(input: &str) -> (Vec<u64>, Vec<Phase>) {
 struct Phase {
     #[allow(dead_code)]
     name: String,
-    spans: Vec<Span>,
+    map: RangeMap<u64>,
 }
 
 impl Phase {
@@ -96,7 +84,7 @@ impl Phase {
             .trim()
             .strip_suffix(" map:")
             .expect("is a map name line");
-        let mut spans = Vec::new();
+        let mut map = RangeMap::new();
         for l in lines {
             let l = l.trim();
             if l.is_empty() {
@@ -107,110 +95,13 @@ impl Phase {
                 .map(|x| x.parse::<u64>().expect("parse number"))
                 .collect_tuple()
                 .expect("line has 3 fields");
-            spans.push(Span {
-                dest_start,
-                src_start,
-                len,
-            })
+            map.insert(src_start..(src_start + len), dest_start);
         }
         Some(Phase {
             name: name.to_owned(),
-            spans,
+            map,
         })
     }
-
-    /// Given a source index return the corresponding destination space index.
-    fn lookup(&self, src: u64) -> u64 {
-        self.spans
-            .iter()
-            .filter_map(|r| r.lookup(src))
-            .next()
-            .unwrap_or(src)
-    }
-
-    /// Map a range through all the spans, splitting it into multiple ranges.
-    /// Parts that don't match any span remain unchanged.
-    fn map_range(&self, r: Range) -> Vec<Range> {
-        /* Open question: do the ranges collide? If so, is it important that we dedupe them?
-
-        At least on my input, it turns out not to matter: we never get too many ranges to be a problem,
-        and any potential collisions don't seem to cause this to get the wrong result. */
-        let orig_len = r.end - r.start;
-        let mut matched: Vec<Range> = Vec::new();
-        let mut unmatched: Vec<Range> = vec![r];
-        for span in &self.spans {
-            let mut still_unmatched = Vec::new();
-            for a in unmatched {
-                // TODO: Split out to aoclib::range.
-                if a.end <= span.src_start || a.start >= span.src_end() {
-                    still_unmatched.push(a);
-                } else {
-                    // How much of this is before the span
-                    let len_before = span.src_start.saturating_sub(a.start);
-                    if len_before > 0 {
-                        still_unmatched.push(a.start..(a.start + len_before));
-                    }
-                    let len_after = a.end.saturating_sub(span.src_end());
-                    if len_after > 0 {
-                        still_unmatched.push(span.src_end()..(span.src_end() + len_after));
-                    }
-                    let match_start = max(a.start, span.src_start);
-                    let match_end = min(a.end, span.src_end());
-                    assert!(match_end > match_start);
-                    let match_offset = match_start.checked_sub(span.src_start).unwrap();
-                    let match_len = match_end.checked_sub(match_start).unwrap();
-                    let match_dst_end = span.dest_start + match_offset + match_len;
-                    let match_dst_start = span.dest_start + match_offset;
-                    assert!(span.dest_range().contains(&match_dst_start));
-                    assert!(
-                        match_dst_end <= span.dest_end(),
-                        "{match_dst_end} is not in expected destination range {:?}",
-                        span.dest_range()
-                    );
-                    matched.push(match_dst_start..match_dst_end);
-                }
-            }
-            unmatched = still_unmatched;
-        }
-        // Anything still unmatched is left alone
-        matched.append(&mut unmatched);
-        // Check that we didn't lose anything or collide -- though colliding might potentially be valid
-        // but unspecified...
-        assert_eq!(
-            matched.iter().map(|r| r.end - r.start).sum::<u64>(),
-            orig_len
-        );
-        matched
-    }
-}
-
-#[derive(Debug)]
-struct Span {
-    dest_start: u64,
-    src_start: u64,
-    len: u64,
-}
-
-impl Span {
-    fn lookup(&self, src: u64) -> Option<u64> {
-        if src >= self.src_start && src < (self.src_start + self.len) {
-            Some(self.dest_start + src - self.src_start)
-        } else {
-            None
-        }
-    }
-
-    fn src_end(&self) -> u64 {
-        self.src_start + self.len
-    }
-
-    fn dest_end(&self) -> u64 {
-        self.dest_start + self.len
-    }
-
-    fn dest_range(&self) -> Range {
-        self.dest_start..self.dest_end()
-    }
 }
 
 #[cfg(test)]
@@ -277,18 +168,12 @@ mod test {
 
     #[test]
     fn test_map_range() {
-        let phase = Phase {
-            name: "a-to-b".to_owned(),
-            spans: vec![Span {
-                src_start: 10,
-                dest_start: 20,
-                len: 5,
-            }],
-        };
-        assert_eq!(phase.map_range(0..5), vec![(0..5)]);
-        assert_eq!(phase.map_range(0..9), vec![(0..9)]);
-        assert_eq!(phase.map_range(0..10), vec![(0..10)]); // range is semi-open; 10 isn't mapped
-        assert_eq!(phase.map_range(0..11), vec![20..21, 0..10]);
-        assert_eq!(phase.map_range(0..20), vec![20..25, 0..10, 15..20]);
+        let map: RangeMap<u64> = [(10..15, 20)].into_iter().collect();
+        let apply = |r: std::ops::Range<u64>| map.apply(&IntervalSet::from(r)).ranges().to_vec();
+        assert_eq!(apply(0..5), vec![(0..5)]);
+        assert_eq!(apply(0..9), vec![(0..9)]);
+        assert_eq!(apply(0..10), vec![(0..10)]); // range is semi-open; 10 isn't mapped
+        assert_eq!(apply(0..11), vec![0..10, 20..21]);
+        assert_eq!(apply(0..20), vec![0..10, 15..25]);
     }
 }
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 use std::fs::read_to_string;
 
+use aoclib::numtheory::lcm_all;
 use itertools::Itertools;
-use num_integer::lcm;
 
 static YEAR: &str = "2023";
 static DAY: &str = "08";
@@ -103,7 +103,7 @@ fn solve_b(input: &str) -> usize {
             num_prime::nt_funcs::factorize64(*cycle as u64)
         );
     }
-    cycles.iter().fold(1, |a, b| lcm(a, *b))
+    lcm_all(cycles)
 }
 
 fn is_z(pos: &str) -> bool {
@@ -1,3 +1,5 @@
+use aoclib::Memo;
+
 static PUZZLE: &str = env!("CARGO_BIN_NAME");
 
 fn main() {
@@ -11,15 +13,56 @@ fn input() -> String {
 }
 
 fn solve_a(input: &str) -> usize {
-    /* We can definitely proceed one line at a time and then sum them up.
-     */
-    let _ = input;
-    todo!()
+    // We can definitely proceed one line at a time and then sum them up.
+    parse(input)
+        .map(|(springs, groups)| arrangements(springs.as_bytes(), &groups))
+        .sum()
 }
 
 fn solve_b(input: &str) -> usize {
-    let _ = input;
-    0
+    parse(input)
+        .map(|(springs, groups)| {
+            let springs = [springs; 5].join("?");
+            arrangements(springs.as_bytes(), &groups.repeat(5))
+        })
+        .sum()
+}
+
+fn parse(input: &str) -> impl Iterator<Item = (&str, Vec<usize>)> {
+    input.lines().map(|l| {
+        let (springs, groups) = l.split_once(' ').expect("line has two fields");
+        let groups = groups
+            .split(',')
+            .map(|g| g.parse().expect("parse group"))
+            .collect();
+        (springs, groups)
+    })
+}
+
+/// Count the ways the unknown `?` springs could be filled in to give
+/// contiguous groups of damaged `#` springs with the given lengths.
+fn arrangements(springs: &[u8], groups: &[usize]) -> usize {
+    // Work along from the left: the first spring either starts the next
+    // group, or it's operational and can be skipped.
+    let mut memo = Memo::new();
+    memo.recurse((springs, groups), &|count, (s, g)| {
+        let Some(&first) = s.first() else {
+            return usize::from(g.is_empty());
+        };
+        let mut n = 0;
+        if first != b'#' {
+            n += count((&s[1..], g));
+        }
+        if first != b'.'
+            && let Some((&len, rest)) = g.split_first()
+            && s.len() >= len
+            && !s[..len].contains(&b'.')
+            && s.get(len) != Some(&b'#')
+        {
+            n += count((&s[(len + 1).min(s.len())..], rest));
+        }
+        n
+    })
 }
 
 #[cfg(test)]
@@ -38,11 +81,15 @@ mod test {
     " };
 
     #[test]
-    #[ignore = "not working yet"]
     fn example_1() {
         assert_eq!(solve_a(EXAMPLE), 21);
     }
 
+    #[test]
+    fn example_2() {
+        assert_eq!(solve_b(EXAMPLE), 525152);
+    }
+
     #[test]
     fn solution_a() {
         // assert_eq!(solve_a(&input()), 24706);
@@ -1,3 +1,4 @@
+use aoclib::cycle::find_cycle;
 use aoclib::{Dir, Matrix, input};
 
 fn main() {
@@ -14,28 +15,20 @@ fn solve_one(input: &str) -> usize {
 }
 
 fn solve_two(input: &str) -> usize {
-    let mut map = Matrix::from_string_lines(input);
     static MANY_CYCLES: usize = 1000000000;
-    // To calculate this many cycles let's try to find a recurring pattern in the map:
-    // once we find that meta-cycle we should be able to extrapolate where it will end up.
-    let mut history = Vec::new();
-    for i in 0..300 {
-        slide(&mut map, Dir::N);
-        slide(&mut map, Dir::W);
-        slide(&mut map, Dir::S);
-        slide(&mut map, Dir::E);
-        if let Some(recur) = history.iter().enumerate().rev().find(|(_i, v)| **v == map) {
-            let prev_i = recur.0;
-            // println!("found recurrence from {prev_i} to {i}");
-            let metacycle_len = i - prev_i;
-            let end_on = ((MANY_CYCLES - prev_i - 1) % metacycle_len) + prev_i;
-            return calc_load(&history[end_on]);
-        } else {
-            history.push(map.clone());
-            // println!("cycle {i}:\n{}", map.to_string_lines());
-        }
+    // The map soon settles into a recurring pattern, so we only need to run
+    // up to the cycle that ends in the same state as the billionth.
+    let history = find_cycle(Matrix::from_string_lines(input), spin, Clone::clone);
+    calc_load(history.nth(MANY_CYCLES))
+}
+
+/// Slide the rocks north, west, south, and east.
+fn spin(map: &Matrix<char>) -> Matrix<char> {
+    let mut map = map.clone();
+    for dir in [Dir::N, Dir::W, Dir::S, Dir::E] {
+        slide(&mut map, dir);
     }
-    unreachable!()
+    map
 }
 
 /// Slide all round rocks in one direction as far as they will go.
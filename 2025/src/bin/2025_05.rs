@@ -1,3 +1,5 @@
+use aoclib::range::IntervalSet;
+
 fn main() {
     let input = input();
     println!("{}", solve1(&input));
@@ -37,23 +39,10 @@ fn solve1(input: &str) -> usize {
 }
 
 fn solve2(input: &str) -> usize {
-    let Input { mut fresh, .. } = parse(input);
-    fresh.sort();
-    // Proceed through the ranges keeping track of the number of fresh items that
-    // we've seen so far...
-    let mut pos = 0;
-    let mut tot = 0;
-    for (start, end) in fresh {
-        if pos > end {
-        } else if pos < start {
-            tot += end + 1 - start;
-            pos = end + 1;
-        } else {
-            tot += end + 1 - pos;
-            pos = end + 1;
-        }
-    }
-    tot
+    let Input { fresh, .. } = parse(input);
+    // The input ranges are inclusive and may overlap.
+    let fresh: IntervalSet<usize> = fresh.into_iter().map(|(a, b)| a..(b + 1)).collect();
+    fresh.total_len()
 }
 
 #[cfg(test)]
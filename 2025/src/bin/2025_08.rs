@@ -1,5 +1,4 @@
-use std::mem::take;
-
+use aoclib::UnionFind;
 use itertools::Itertools;
 
 fn main() {
@@ -53,51 +52,27 @@ fn cross_distances(points: &[Point]) -> Vec<(usize, usize, usize)> {
 
 fn solve1(input: &str, rounds: usize) -> usize {
     let points: Vec<Point> = parse(input);
-    // map from point number to circuit number: initially each point is in a distinct circuit
-    let mut circuits = (0..points.len()).collect::<Vec<usize>>();
-    // make a list of distances between distinct pairs of points
-    let dists = cross_distances(&points);
-    for (_distance, p1, p2) in dists.into_iter().take(rounds) {
-        // println!("closest {i}: {:?}, {:?}", points[p1], points[p2]);
-        let cct1 = circuits[p1];
-        let cct2 = circuits[p2];
-        if cct1 != cct2 {
-            // println!("  join circuits");
-            circuits.iter_mut().for_each(|c| {
-                if *c == cct2 {
-                    *c = cct1
-                }
-            });
-        }
-    }
-    let mut circuitsizes = vec![0; points.len()];
-    for cct in circuits {
-        circuitsizes[cct] += 1;
+    // initially each point is in a distinct circuit
+    let mut circuits = UnionFind::new(points.len());
+    for (_distance, p1, p2) in cross_distances(&points).into_iter().take(rounds) {
+        circuits.union(p1, p2);
     }
-    circuitsizes.iter().sorted().rev().take(3).product()
+    circuits
+        .component_sizes()
+        .into_iter()
+        .sorted()
+        .rev()
+        .take(3)
+        .product()
 }
 
 fn solve2(input: &str) -> usize {
     let points: Vec<Point> = parse(input);
-    let npoints = points.len();
-    // map from point number to circuit number: initially each point is in a distinct circuit
-    let mut circuits = (0..npoints).collect::<Vec<usize>>();
-    let mut circuitsizes = vec![1; npoints];
+    // initially each point is in a distinct circuit
+    let mut circuits = UnionFind::new(points.len());
     for (_distance, p1, p2) in cross_distances(&points) {
-        // println!("closest {i}: {:?}, {:?}", points[p1], points[p2]);
-        let cct1 = circuits[p1];
-        let cct2 = circuits[p2];
-        if cct1 != cct2 {
-            // println!("  join circuits");
-            circuits.iter_mut().for_each(|c| {
-                if *c == cct2 {
-                    *c = cct1
-                }
-            });
-            circuitsizes[cct1] += take(&mut circuitsizes[cct2]);
-            if circuitsizes[cct1] == npoints {
-                return points[p1][0] * points[p2][0];
-            }
+        if circuits.union(p1, p2) && circuits.component_count() == 1 {
+            return points[p1][0] * points[p2][0];
         }
     }
     unreachable!("Didn't merge all the points?");
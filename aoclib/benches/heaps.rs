@@ -0,0 +1,130 @@
+// Copyright 2026 Martin Pool
+
+//! Compare heap strategies for Djikstra's method on a weighted grid.
+//!
+//! Run with `cargo bench -p aoclib`. This uses no benchmark framework: each
+//! strategy is timed over a few runs and the fastest is reported.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoclib::{point, HeapHandle, IndexedMinHeap, Matrix, MinHeap, Point, RadixHeap};
+
+const SIZE: usize = 300;
+const RUNS: usize = 5;
+
+/// A grid where entering each cell costs 1 to 9, from a fixed pseudo-random sequence.
+fn grid() -> Matrix<u64> {
+    let mut m = Matrix::new(SIZE, SIZE, 0);
+    let mut x: u64 = 12345;
+    for p in m.points().collect::<Vec<_>>() {
+        x = x
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        m[p] = (x >> 60) % 9 + 1;
+    }
+    m
+}
+
+fn neighbors(m: &Matrix<u64>, p: Point) -> impl Iterator<Item = (Point, u64)> + '_ {
+    m.neighbors4(p).map(|(q, c)| (q, *c))
+}
+
+/// The current strategy in `ShortestPath`: push duplicates and skip stale entries.
+fn duplicate_push(m: &Matrix<u64>, start: Point, end: Point) -> u64 {
+    let mut best: HashMap<Point, u64> = HashMap::from([(start, 0)]);
+    let mut heap = MinHeap::new();
+    heap.push((0, start));
+    while let Some((d, p)) = heap.pop() {
+        if p == end {
+            return d;
+        }
+        if d > best[&p] {
+            continue;
+        }
+        for (q, c) in neighbors(m, p) {
+            let nd = d + c;
+            if best.get(&q).is_none_or(|&b| nd < b) {
+                best.insert(q, nd);
+                heap.push((nd, q));
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Keep one entry per point and lower its priority when a shorter path is found.
+fn decrease_key(m: &Matrix<u64>, start: Point, end: Point) -> u64 {
+    let mut handles: HashMap<Point, HeapHandle> = HashMap::new();
+    let mut done: HashMap<Point, u64> = HashMap::new();
+    let mut heap = IndexedMinHeap::new();
+    handles.insert(start, heap.push(start, 0));
+    while let Some((p, d)) = heap.pop() {
+        done.insert(p, d);
+        if p == end {
+            return d;
+        }
+        for (q, c) in neighbors(m, p) {
+            if done.contains_key(&q) {
+                continue;
+            }
+            let nd = d + c;
+            match handles.get(&q) {
+                Some(&h) if heap.contains(h) => {
+                    heap.decrease_key(h, nd);
+                }
+                _ => {
+                    handles.insert(q, heap.push(q, nd));
+                }
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Push duplicates onto a radix heap, which suits small integer step costs.
+fn radix(m: &Matrix<u64>, start: Point, end: Point) -> u64 {
+    let mut best: HashMap<Point, u64> = HashMap::from([(start, 0)]);
+    let mut heap = RadixHeap::new();
+    heap.push(0, start);
+    while let Some((d, p)) = heap.pop() {
+        if p == end {
+            return d;
+        }
+        if d > best[&p] {
+            continue;
+        }
+        for (q, c) in neighbors(m, p) {
+            let nd = d + c;
+            if best.get(&q).is_none_or(|&b| nd < b) {
+                best.insert(q, nd);
+                heap.push(nd, q);
+            }
+        }
+    }
+    unreachable!()
+}
+
+fn time(name: &str, f: impl Fn() -> u64) -> u64 {
+    let mut fastest = Duration::MAX;
+    let mut result = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        result = black_box(f());
+        fastest = fastest.min(start.elapsed());
+    }
+    println!("{name:>16}: {fastest:?}");
+    result
+}
+
+fn main() {
+    let m = grid();
+    let (start, end) = (point(0, 0), point(SIZE as isize - 1, SIZE as isize - 1));
+    println!("Djikstra across a {SIZE}x{SIZE} grid, best of {RUNS} runs:");
+    let a = time("duplicate push", || duplicate_push(&m, start, end));
+    let b = time("decrease key", || decrease_key(&m, start, end));
+    let c = time("radix heap", || radix(&m, start, end));
+    assert_eq!(a, b);
+    assert_eq!(a, c);
+}
@@ -0,0 +1,486 @@
+// Copyright 2026 Martin Pool
+
+//! Signed integers of unlimited size, for the occasional answer that doesn't
+//! fit in 128 bits.
+//!
+//! This is simple rather than fast: multiplication and division take time
+//! proportional to the square of the length.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A signed integer of any size.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    /// True for values below zero. Zero is never negative.
+    negative: bool,
+    /// The absolute value in base 2^32, least significant digit first,
+    /// without any trailing zero digits: zero is empty.
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[must_use]
+    pub fn abs(&self) -> BigInt {
+        BigInt {
+            negative: false,
+            mag: self.mag.clone(),
+        }
+    }
+
+    /// Raise this to the power `exp`.
+    #[must_use]
+    pub fn pow(&self, mut exp: u32) -> BigInt {
+        let mut result = BigInt::from(1);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= &base;
+            }
+            base = &base * &base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Return the value as an `i128`, or None if it's too big.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.mag.len() > 4 {
+            return None;
+        }
+        let m = self
+            .mag
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &d| (acc << 32) | d as u128);
+        if self.negative {
+            0i128.checked_sub_unsigned(m)
+        } else {
+            i128::try_from(m).ok()
+        }
+    }
+
+    /// Divide, returning the quotient rounded towards zero and the
+    /// remainder, which has the same sign as `self`.
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "division by zero");
+        let (q, r) = div_rem_mag(&self.mag, &divisor.mag);
+        (
+            BigInt::from_parts(self.negative != divisor.negative, q),
+            BigInt::from_parts(self.negative, r),
+        )
+    }
+
+    /// Make a value from a sign and a magnitude that might have trailing zeros.
+    fn from_parts(negative: bool, mut mag: Vec<u32>) -> BigInt {
+        while mag.last() == Some(&0) {
+            mag.pop();
+        }
+        BigInt {
+            negative: negative && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    fn from_u128(negative: bool, mut m: u128) -> BigInt {
+        let mut mag = Vec::new();
+        while m > 0 {
+            mag.push(m as u32);
+            m >>= 32;
+        }
+        BigInt::from_parts(negative, mag)
+    }
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &d) in long.iter().enumerate() {
+        let sum = d as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    result.push(carry as u32);
+    result
+}
+
+/// Subtract magnitudes where `a >= b`.
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    debug_assert!(cmp_mag(a, b) != Ordering::Less);
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &d) in a.iter().enumerate() {
+        let mut diff = d as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = i64::from(diff < 0);
+        if diff < 0 {
+            diff += 1 << 32;
+        }
+        result.push(diff as u32);
+    }
+    result
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
+
+/// Long division of magnitudes, one bit at a time.
+fn div_rem_mag(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut q = vec![0u32; a.len()];
+    let mut r: Vec<u32> = Vec::new();
+    for i in (0..a.len() * 32).rev() {
+        // r = r * 2 + the next bit of a.
+        let mut carry = (a[i / 32] >> (i % 32)) & 1;
+        for d in r.iter_mut() {
+            let next = *d >> 31;
+            *d = (*d << 1) | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            r.push(carry);
+        }
+        if cmp_mag(&r, b) != Ordering::Less {
+            r = sub_mag(&r, b);
+            while r.last() == Some(&0) {
+                r.pop();
+            }
+            q[i / 32] |= 1 << (i % 32);
+        }
+    }
+    (q, r)
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for BigInt {
+            fn from(v: $t) -> BigInt {
+                BigInt::from_u128(v < 0, (v as i128).unsigned_abs())
+            }
+        })*
+    };
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for BigInt {
+            fn from(v: $t) -> BigInt {
+                BigInt::from_u128(false, v as u128)
+            }
+        })*
+    };
+}
+
+from_signed!(i8, i16, i32, i64, i128, isize);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_mag(&self.mag, &rhs.mag));
+        }
+        // Opposite signs: the result has the sign of the larger magnitude.
+        match cmp_mag(&self.mag, &rhs.mag) {
+            Ordering::Less => BigInt::from_parts(rhs.negative, sub_mag(&rhs.mag, &self.mag)),
+            _ => BigInt::from_parts(self.negative, sub_mag(&self.mag, &rhs.mag)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != rhs.negative, mul_mag(&self.mag, &rhs.mag))
+    }
+}
+
+/// Division rounds towards zero, like the primitive integers.
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).1
+    }
+}
+
+/// Implement the operators on owned values in terms of references.
+macro_rules! owned_ops {
+    ($($tr:ident $f:ident $assign_tr:ident $assign_f:ident),*) => {
+        $(
+            impl $tr for BigInt {
+                type Output = BigInt;
+
+                fn $f(self, rhs: BigInt) -> BigInt {
+                    (&self).$f(&rhs)
+                }
+            }
+
+            impl $tr<&BigInt> for BigInt {
+                type Output = BigInt;
+
+                fn $f(self, rhs: &BigInt) -> BigInt {
+                    (&self).$f(rhs)
+                }
+            }
+
+            impl $assign_tr<&BigInt> for BigInt {
+                fn $assign_f(&mut self, rhs: &BigInt) {
+                    *self = (&*self).$f(rhs);
+                }
+            }
+
+            impl $assign_tr for BigInt {
+                fn $assign_f(&mut self, rhs: BigInt) {
+                    *self = (&*self).$f(&rhs);
+                }
+            }
+        )*
+    };
+}
+
+owned_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign
+);
+
+impl Div for BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: BigInt) -> BigInt {
+        &self / &rhs
+    }
+}
+
+impl Rem for BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: BigInt) -> BigInt {
+        &self % &rhs
+    }
+}
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::zero(), |a, b| a + b)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::from(1), |a, b| a * b)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peel off nine decimal digits at a time.
+        const CHUNK: u32 = 1_000_000_000;
+        let mut chunks = Vec::new();
+        let mut mag = self.mag.clone();
+        while !mag.is_empty() {
+            let mut rem = 0u64;
+            for d in mag.iter_mut().rev() {
+                let cur = (rem << 32) | *d as u64;
+                *d = (cur / CHUNK as u64) as u32;
+                rem = cur % CHUNK as u64;
+            }
+            chunks.push(rem as u32);
+            while mag.last() == Some(&0) {
+                mag.pop();
+            }
+        }
+        let mut s = String::new();
+        match chunks.split_last() {
+            None => s.push('0'),
+            Some((first, rest)) => {
+                s += &first.to_string();
+                for c in rest.iter().rev() {
+                    s += &format!("{c:09}");
+                }
+            }
+        }
+        f.pad_integral(!self.negative, "", &s)
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = &'static str;
+
+    /// Parse a decimal number, optionally starting with a sign.
+    fn from_str(s: &str) -> Result<BigInt, &'static str> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("invalid digit in number");
+        }
+        let ten = BigInt::from(10);
+        let mut result = BigInt::zero();
+        for b in digits.bytes() {
+            result = result * &ten + BigInt::from(b - b'0');
+        }
+        Ok(if negative { -result } else { result })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn factorial_of_30() {
+        let f: BigInt = (1..=30).map(BigInt::from).product();
+        assert_eq!(f.to_string(), "265252859812191058636308480000000");
+        assert_eq!(f.to_i128(), Some(265252859812191058636308480000000));
+        let big = f.pow(3);
+        assert_eq!(big.to_i128(), None);
+        assert_eq!(&(&big / &f) / &f, f);
+        assert_eq!(&big % &f, BigInt::zero());
+        assert_eq!(
+            "-18662947087877997278870948751570073201489891556132852543277996696313572360192000000000000000000000"
+                .parse::<BigInt>()
+                .unwrap(),
+            -big
+        );
+    }
+
+    #[test]
+    fn extremes_of_i128() {
+        for v in [i128::MIN, i128::MAX, 0, -1, 1] {
+            let b = BigInt::from(v);
+            assert_eq!(b.to_i128(), Some(v));
+            assert_eq!(b.to_string(), v.to_string());
+        }
+        assert_eq!((BigInt::from(i128::MAX) + BigInt::from(1)).to_i128(), None);
+        assert_eq!((BigInt::from(i128::MIN) - BigInt::from(1)).to_i128(), None);
+        assert_eq!(format!("{:>5}", BigInt::from(-12)), "  -12");
+        assert!("12a".parse::<BigInt>().is_err());
+        assert!("-".parse::<BigInt>().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_i128(a: i64, b: i64) {
+            let (ba, bb) = (BigInt::from(a), BigInt::from(b));
+            let (a, b) = (a as i128, b as i128);
+            prop_assert_eq!((&ba + &bb).to_i128(), Some(a + b));
+            prop_assert_eq!((&ba - &bb).to_i128(), Some(a - b));
+            prop_assert_eq!((&ba * &bb).to_i128(), Some(a * b));
+            prop_assert_eq!(ba.cmp(&bb), a.cmp(&b));
+            prop_assert_eq!(ba.to_string(), a.to_string());
+            prop_assert_eq!(ba.to_string().parse::<BigInt>(), Ok(ba.clone()));
+            if b != 0 {
+                prop_assert_eq!((&ba / &bb).to_i128(), Some(a / b));
+                prop_assert_eq!((&ba % &bb).to_i128(), Some(a % b));
+            }
+        }
+
+        #[test]
+        fn division_identity(a: u128, b: u128, c: u64, neg: bool) {
+            // (a * b + c) / b == a, when c < b.
+            prop_assume!(b > c as u128);
+            let a = if neg { -BigInt::from(a) } else { BigInt::from(a) };
+            let b = BigInt::from(b);
+            let c = BigInt::from(c);
+            let n = &(&a * &b) + &c;
+            let (q, r) = n.div_rem(&b);
+            prop_assert_eq!(&(&q * &b) + &r, n);
+            prop_assert!(r.abs() < b);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright 2026 Martin Pool
+
+//! Axis-aligned boxes in any number of dimensions, and sets of points
+//! made of them.
+
+/// An axis-aligned box of integer points in `D` dimensions.
+///
+/// Like a [std::ops::Range] the box is half-open: it includes its `min`
+/// corner but excludes its `max` corner. It's empty if it has no extent
+/// along any axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<const D: usize> {
+    min: [isize; D],
+    max: [isize; D],
+}
+
+impl<const D: usize> Cuboid<D> {
+    /// Construct a box from its lowest corner and the corner just past its highest.
+    #[must_use]
+    pub fn new(min: [isize; D], max: [isize; D]) -> Cuboid<D> {
+        Cuboid { min, max }
+    }
+
+    /// Construct a box including both corners, as puzzles often describe them.
+    #[must_use]
+    pub fn from_inclusive(min: [isize; D], max: [isize; D]) -> Cuboid<D> {
+        Cuboid {
+            min,
+            max: max.map(|m| m + 1),
+        }
+    }
+
+    /// The lowest corner, which is included in the box.
+    pub fn min(&self) -> [isize; D] {
+        self.min
+    }
+
+    /// The corner just past the highest, which is not included in the box.
+    pub fn max(&self) -> [isize; D] {
+        self.max
+    }
+
+    /// True if the box contains no points.
+    pub fn is_empty(&self) -> bool {
+        (0..D).any(|i| self.min[i] >= self.max[i])
+    }
+
+    /// The number of points in the box.
+    pub fn volume(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (0..D).map(|i| (self.max[i] - self.min[i]) as u64).product()
+        }
+    }
+
+    /// True if `p` is inside the box.
+    pub fn contains(&self, p: &[isize; D]) -> bool {
+        (0..D).all(|i| self.min[i] <= p[i] && p[i] < self.max[i])
+    }
+
+    /// True if every point of `other` is inside this box.
+    pub fn contains_cuboid(&self, other: &Cuboid<D>) -> bool {
+        other.is_empty()
+            || (0..D).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+
+    /// True if the two boxes have any points in common.
+    pub fn intersects(&self, other: &Cuboid<D>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Return the points in both boxes, or None if they don't overlap.
+    pub fn intersection(&self, other: &Cuboid<D>) -> Option<Cuboid<D>> {
+        let c = Cuboid {
+            min: std::array::from_fn(|i| self.min[i].max(other.min[i])),
+            max: std::array::from_fn(|i| self.max[i].min(other.max[i])),
+        };
+        (!c.is_empty()).then_some(c)
+    }
+
+    /// Return disjoint boxes covering the points of this box that are not in `other`.
+    ///
+    /// There are at most `2 * D` pieces.
+    pub fn subtract(&self, other: &Cuboid<D>) -> Vec<Cuboid<D>> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() {
+                Vec::new()
+            } else {
+                vec![*self]
+            };
+        };
+        // Along each axis in turn, slice off the slabs below and above the cut,
+        // and carry on with what remains between them.
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for i in 0..D {
+            if rest.min[i] < cut.min[i] {
+                let (below, above) = rest.split(i, cut.min[i]);
+                pieces.push(below);
+                rest = above;
+            }
+            if cut.max[i] < rest.max[i] {
+                let (below, above) = rest.split(i, cut.max[i]);
+                pieces.push(above);
+                rest = below;
+            }
+        }
+        debug_assert_eq!(rest, cut);
+        pieces
+    }
+
+    /// Split the box by a plane across `axis`, into the parts below `at`
+    /// and from `at` upwards.
+    ///
+    /// One part is empty if `at` is outside the box.
+    pub fn split(&self, axis: usize, at: isize) -> (Cuboid<D>, Cuboid<D>) {
+        let at = at.clamp(self.min[axis], self.max[axis]);
+        let mut below = *self;
+        let mut above = *self;
+        below.max[axis] = at;
+        above.min[axis] = at;
+        (below, above)
+    }
+
+    /// Split the box in half along every axis, as in a quadtree or octree,
+    /// returning up to `2^D` non-empty children.
+    ///
+    /// Axes of length 1 are not split, so a box of one point is returned whole.
+    pub fn subdivide(&self) -> Vec<Cuboid<D>> {
+        let mut parts = vec![*self];
+        for i in 0..D {
+            let mid = self.min[i] + (self.max[i] - self.min[i]) / 2;
+            parts = parts
+                .into_iter()
+                .flat_map(|c| {
+                    let (a, b) = c.split(i, mid);
+                    [a, b]
+                })
+                .filter(|c| !c.is_empty())
+                .collect();
+        }
+        parts
+    }
+}
+
+/// A set of points represented as disjoint boxes.
+///
+/// This can turn large boxes on and off without needing space for every
+/// point, or compressing the coordinates that are used.
+#[derive(Clone, Debug)]
+pub struct CuboidSet<const D: usize> {
+    cuboids: Vec<Cuboid<D>>,
+}
+
+impl<const D: usize> Default for CuboidSet<D> {
+    fn default() -> Self {
+        CuboidSet {
+            cuboids: Vec::new(),
+        }
+    }
+}
+
+impl<const D: usize> CuboidSet<D> {
+    /// Construct an empty set.
+    pub fn new() -> CuboidSet<D> {
+        CuboidSet::default()
+    }
+
+    /// Add all the points of `c` to the set.
+    pub fn insert(&mut self, c: Cuboid<D>) {
+        self.remove(&c);
+        if !c.is_empty() {
+            self.cuboids.push(c);
+        }
+    }
+
+    /// Remove all the points of `c` from the set.
+    pub fn remove(&mut self, c: &Cuboid<D>) {
+        if !self.cuboids.iter().any(|b| b.intersects(c)) {
+            return;
+        }
+        self.cuboids = self.cuboids.iter().flat_map(|b| b.subtract(c)).collect();
+    }
+
+    /// True if `p` is in the set.
+    pub fn contains(&self, p: &[isize; D]) -> bool {
+        self.cuboids.iter().any(|c| c.contains(p))
+    }
+
+    /// The number of points in the set.
+    pub fn volume(&self) -> u64 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    /// True if the set contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    /// Iterate the disjoint boxes that make up the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Cuboid<D>> {
+        self.cuboids.iter()
+    }
+
+    /// Return the points of the set that are inside `bounds`.
+    pub fn clipped(&self, bounds: &Cuboid<D>) -> CuboidSet<D> {
+        CuboidSet {
+            cuboids: self
+                .cuboids
+                .iter()
+                .filter_map(|c| c.intersection(bounds))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn volume_and_intersection() {
+        let a = Cuboid::new([0, 0, 0], [4, 3, 2]);
+        assert_eq!(a.volume(), 24);
+        assert!(a.contains(&[3, 2, 1]));
+        assert!(!a.contains(&[4, 0, 0]));
+        let b = Cuboid::from_inclusive([2, 2, 1], [10, 10, 10]);
+        assert_eq!(a.intersection(&b), Some(Cuboid::new([2, 2, 1], [4, 3, 2])));
+        let c = Cuboid::new([4, 0, 0], [5, 1, 1]);
+        assert!(!a.intersects(&c));
+        assert!(Cuboid::new([1, 1], [1, 5]).is_empty());
+        assert_eq!(Cuboid::new([3, 0], [1, 5]).volume(), 0);
+    }
+
+    #[test]
+    fn subtract_makes_a_hole() {
+        let outer = Cuboid::new([0, 0, 0], [3, 3, 3]);
+        let hole = Cuboid::new([1, 1, 1], [2, 2, 2]);
+        let pieces = outer.subtract(&hole);
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<u64>(), 26);
+        assert!(pieces.iter().all(|p| !p.intersects(&hole)));
+        assert!(hole.subtract(&outer).is_empty());
+        assert!(outer.contains_cuboid(&hole));
+    }
+
+    #[test]
+    fn subdivide_octree() {
+        let c = Cuboid::new([0, 0, 0], [4, 4, 3]);
+        let children = c.subdivide();
+        assert_eq!(children.len(), 8);
+        assert_eq!(children.iter().map(Cuboid::volume).sum::<u64>(), 48);
+        assert_eq!(Cuboid::new([5, 5], [6, 6]).subdivide().len(), 1);
+    }
+
+    fn cuboid() -> impl Strategy<Value = Cuboid<2>> {
+        ([0..8isize, 0..8isize], [0..6isize, 0..6isize])
+            .prop_map(|(min, size)| Cuboid::new(min, [min[0] + size[0], min[1] + size[1]]))
+    }
+
+    fn points(c: &Cuboid<2>) -> HashSet<[isize; 2]> {
+        let mut s = HashSet::new();
+        for x in c.min()[0]..c.max()[0] {
+            for y in c.min()[1]..c.max()[1] {
+                s.insert([x, y]);
+            }
+        }
+        s
+    }
+
+    proptest! {
+        #[test]
+        fn subtract_agrees_with_points(a in cuboid(), b in cuboid()) {
+            let pieces = a.subtract(&b);
+            let mut covered = HashSet::new();
+            for p in &pieces {
+                prop_assert!(!p.is_empty());
+                for q in points(p) {
+                    prop_assert!(covered.insert(q), "pieces overlap");
+                }
+            }
+            prop_assert_eq!(covered, &points(&a) - &points(&b));
+            prop_assert_eq!(
+                a.intersection(&b).map(|c| points(&c)).unwrap_or_default(),
+                &points(&a) & &points(&b)
+            );
+        }
+
+        #[test]
+        fn set_agrees_with_points(ops in prop::collection::vec((any::<bool>(), cuboid()), 0..12)) {
+            let mut set = CuboidSet::new();
+            let mut model = HashSet::new();
+            for (on, c) in ops {
+                if on {
+                    set.insert(c);
+                    model.extend(points(&c));
+                } else {
+                    set.remove(&c);
+                    model.retain(|p| !c.contains(p));
+                }
+                prop_assert_eq!(set.volume(), model.len() as u64);
+            }
+            for x in 0..15 {
+                for y in 0..15 {
+                    prop_assert_eq!(set.contains(&[x, y]), model.contains(&[x, y]));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright 2026 Martin Pool
+
+//! Find where a sequence of states starts repeating, so that a simulation
+//! that's meant to run for a billion steps can skip ahead.
+//!
+//! Each of these takes an initial state, a `step` function producing the
+//! next state, and a `key` function projecting the part of the state that
+//! determines the future. The key can just be a copy of the whole state,
+//! with `Clone::clone`, or it can leave out counters or scores that keep
+//! growing even when the rest of the state repeats.
+//!
+//! [find_cycle] remembers every state, which makes it simple to look up the
+//! state after any number of steps. [brent] and [floyd] use constant memory,
+//! but call `step` more often.
+//!
+//! All of them loop forever if the sequence never repeats.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence starts repeating.
+///
+/// States `start` and `start + period` are the first pair of states with the same key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before the first state that's in the cycle.
+    pub start: usize,
+    /// Number of steps after which states repeat.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Return the earliest step with the same key as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Return the number of complete periods that have passed by step `n`.
+    pub fn periods_before(&self, n: usize) -> usize {
+        n.saturating_sub(self.start) / self.period
+    }
+
+    /// Return a state with the same key as step `n` of the sequence, by
+    /// running only up to the equivalent step.
+    pub fn fast_forward<S, F>(&self, init: S, mut step: F, n: usize) -> S
+    where
+        F: FnMut(&S) -> S,
+    {
+        let mut s = init;
+        for _ in 0..self.equivalent_step(n) {
+            s = step(&s);
+        }
+        s
+    }
+}
+
+/// States of a sequence up to where it starts repeating, remembered by [find_cycle].
+#[derive(Clone, Debug)]
+pub struct CycleHistory<S> {
+    cycle: Cycle,
+    /// States from the start, up to and including the first repeated state.
+    states: Vec<S>,
+}
+
+impl<S> CycleHistory<S> {
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// All the states seen, from the initial state up to and including the
+    /// first repeat, at index `start + period`.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// Return a state with the same key as the state after `n` steps.
+    pub fn nth(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+
+    /// Return the value of `score` after `n` steps, when the score grows by
+    /// the same amount every period even though the key repeats.
+    ///
+    /// For example, the key might be the shape of a pattern that drifts
+    /// sideways, with a score that depends on its position.
+    pub fn extrapolate<F>(&self, n: usize, mut score: F) -> i64
+    where
+        F: FnMut(&S) -> i64,
+    {
+        let Cycle { start, period } = self.cycle;
+        let drift = score(&self.states[start + period]) - score(&self.states[start]);
+        score(self.nth(n)) + drift * self.cycle.periods_before(n) as i64
+    }
+}
+
+/// Step through the sequence, remembering the keys of all states, until a
+/// key is seen for the second time.
+pub fn find_cycle<S, K, F, P>(init: S, mut step: F, mut key: P) -> CycleHistory<S>
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    P: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![init];
+    loop {
+        let last = states.last().unwrap();
+        match seen.entry(key(last)) {
+            Entry::Occupied(e) => {
+                let start = *e.get();
+                let cycle = Cycle {
+                    start,
+                    period: states.len() - 1 - start,
+                };
+                return CycleHistory { cycle, states };
+            }
+            Entry::Vacant(e) => {
+                e.insert(states.len() - 1);
+            }
+        }
+        let next = step(last);
+        states.push(next);
+    }
+}
+
+/// Find the cycle with Brent's algorithm, which keeps only two states at a
+/// time and takes fewer steps than Floyd's.
+pub fn brent<S, K, F, P>(init: S, mut step: F, mut key: P) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+    F: FnMut(&S) -> S,
+    P: FnMut(&S) -> K,
+{
+    // Find the period by moving the hare ahead in ever-doubling stretches,
+    // with the tortoise waiting at the start of each stretch.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = key(&init);
+    let mut hare = step(&init);
+    let mut hare_key = key(&hare);
+    while tortoise != hare_key {
+        if power == period {
+            tortoise = hare_key;
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        hare_key = key(&hare);
+        period += 1;
+    }
+    // Then with the hare one period ahead, they first meet at the start.
+    let start = meet(init, period, &mut step, &mut key);
+    Cycle { start, period }
+}
+
+/// Find the cycle with Floyd's tortoise and hare algorithm, which keeps only
+/// two states at a time.
+pub fn floyd<S, K, F, P>(init: S, mut step: F, mut key: P) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+    F: FnMut(&S) -> S,
+    P: FnMut(&S) -> K,
+{
+    // The hare moves twice as fast, so they meet at some multiple of the period.
+    let mut tortoise = step(&init);
+    let mut hare = step(&tortoise);
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        let h = step(&hare);
+        hare = step(&h);
+    }
+    let mut period = 1;
+    let k = key(&tortoise);
+    hare = step(&tortoise);
+    while key(&hare) != k {
+        hare = step(&hare);
+        period += 1;
+    }
+    let start = meet(init, period, &mut step, &mut key);
+    Cycle { start, period }
+}
+
+/// Return the first step whose key is the same as the step `period` later.
+fn meet<S, K, F, P>(init: S, period: usize, step: &mut F, key: &mut P) -> usize
+where
+    S: Clone,
+    K: Eq,
+    F: FnMut(&S) -> S,
+    P: FnMut(&S) -> K,
+{
+    let mut tortoise = init.clone();
+    let mut hare = init;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    start
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn squares_mod_n() {
+        // 2, 5, 26, 677, 330, 901, 802, 205, 26, ...
+        let step = |x: &u32| (x * x + 1) % 1000;
+        let history = find_cycle(2, step, Clone::clone);
+        let cycle = history.cycle();
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 2,
+                period: 6
+            }
+        );
+        assert_eq!(brent(2, step, Clone::clone), cycle);
+        assert_eq!(floyd(2, step, Clone::clone), cycle);
+        assert_eq!(history.states()[..3], [2, 5, 26]);
+        assert_eq!(*history.nth(1_000_000_000), 330);
+        let mut x = 2;
+        for _ in 0..1000 {
+            x = step(&x);
+        }
+        assert_eq!(*history.nth(1000), x);
+        assert_eq!(cycle.fast_forward(2, step, 1000), x);
+    }
+
+    #[test]
+    fn extrapolate_drift() {
+        // A walker that paces back and forth over 3 cells while drifting
+        // right by 2 every 4 steps.
+        let moves = [1, 1, -1, 1];
+        let step = |&(i, x): &(usize, i64)| ((i + 1) % 4, x + moves[i]);
+        let history = find_cycle((0, 10), step, |s| s.0);
+        assert_eq!(
+            history.cycle(),
+            Cycle {
+                start: 0,
+                period: 4
+            }
+        );
+        let mut s = (0, 10);
+        for n in 0..50 {
+            assert_eq!(history.extrapolate(n, |s| s.1), s.1, "step {n}");
+            s = step(&s);
+        }
+        assert_eq!(history.extrapolate(4_000_000_001, |s| s.1), 2_000_000_011);
+    }
+
+    proptest! {
+        #[test]
+        fn algorithms_agree(table in prop::collection::vec(0..20usize, 20), init in 0..20usize) {
+            let step = |x: &usize| table[*x];
+            let history = find_cycle(init, step, Clone::clone);
+            let cycle = history.cycle();
+            prop_assert_eq!(brent(init, step, Clone::clone), cycle);
+            prop_assert_eq!(floyd(init, step, Clone::clone), cycle);
+            // Check against the definition.
+            let states = history.states();
+            prop_assert_eq!(states[cycle.start], states[cycle.start + cycle.period]);
+            for (i, a) in states[..cycle.start + cycle.period].iter().enumerate() {
+                prop_assert!(!states[..i].contains(a));
+            }
+            let mut x = init;
+            for n in 0..60 {
+                prop_assert_eq!(*history.nth(n), x);
+                x = step(&x);
+            }
+        }
+    }
+}
@@ -0,0 +1,598 @@
+// Copyright 2026 Martin Pool
+
+//! Graphs with explicit nodes and edges, and standard algorithms over them.
+//!
+//! Nodes are identified by a [NodeId] and also have a name, so puzzles that
+//! describe a graph by naming its nodes can be loaded directly:
+//!
+//! ```
+//! use aoclib::graph::Graph;
+//!
+//! let mut g = Graph::<(), ()>::undirected();
+//! for line in ["start-A", "A-end", "start-b", "b-end"] {
+//!     let (a, b) = line.split_once('-').unwrap();
+//!     g.add_edge_named(a, b, ());
+//! }
+//! let (start, end) = (g.node("start"), g.node("end"));
+//! assert_eq!(g.simple_paths(start, end).count(), 2);
+//! ```
+
+use std::cmp::min;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::UnionFind;
+
+mod flow;
+
+pub use flow::MaxFlow;
+
+/// Identifies a node within one [Graph].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The position of the node in the order nodes were added, from 0.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Identifies an edge within one [Graph].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    /// The position of the edge in the order edges were added, from 0.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// An edge and its weight or other data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edge<E> {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub weight: E,
+}
+
+/// A directed or undirected graph with named nodes carrying data of type `N`
+/// and edges carrying data of type `E`.
+///
+/// There may be several edges between the same nodes.
+#[derive(Clone, Debug)]
+pub struct Graph<N, E> {
+    directed: bool,
+    names: Vec<String>,
+    by_name: HashMap<String, NodeId>,
+    data: Vec<N>,
+    edges: Vec<Edge<E>>,
+    /// For each node, the nodes reachable along one edge, and that edge.
+    /// In an undirected graph, every edge appears from both ends.
+    out: Vec<Vec<(NodeId, EdgeId)>>,
+    /// For each node in a directed graph, the nodes with an edge to it.
+    incoming: Vec<Vec<(NodeId, EdgeId)>>,
+}
+
+impl<N, E> Graph<N, E> {
+    /// Make an empty graph whose edges go only from `from` to `to`.
+    pub fn directed() -> Self {
+        Graph::new(true)
+    }
+
+    /// Make an empty graph whose edges go both ways.
+    pub fn undirected() -> Self {
+        Graph::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            names: Vec::new(),
+            by_name: HashMap::new(),
+            data: Vec::new(),
+            edges: Vec::new(),
+            out: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Add a new node.
+    ///
+    /// Panics if there is already a node with this name.
+    pub fn add_node(&mut self, name: &str, data: N) -> NodeId {
+        let id = NodeId(self.names.len());
+        let prev = self.by_name.insert(name.to_owned(), id);
+        assert!(prev.is_none(), "node {name:?} already exists");
+        self.names.push(name.to_owned());
+        self.data.push(data);
+        self.out.push(Vec::new());
+        self.incoming.push(Vec::new());
+        id
+    }
+
+    /// Return the node with this name, if there is one.
+    pub fn find_node(&self, name: &str) -> Option<NodeId> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Add an edge between two existing nodes.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) -> EdgeId {
+        let id = EdgeId(self.edges.len());
+        self.edges.push(Edge { from, to, weight });
+        self.out[from.0].push((to, id));
+        if self.directed {
+            self.incoming[to.0].push((from, id));
+        } else {
+            self.out[to.0].push((from, id));
+        }
+        id
+    }
+
+    pub fn name(&self, node: NodeId) -> &str {
+        &self.names[node.0]
+    }
+
+    pub fn data(&self, node: NodeId) -> &N {
+        &self.data[node.0]
+    }
+
+    pub fn data_mut(&mut self, node: NodeId) -> &mut N {
+        &mut self.data[node.0]
+    }
+
+    pub fn edge(&self, edge: EdgeId) -> &Edge<E> {
+        &self.edges[edge.0]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Iterate all nodes in the order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.names.len()).map(NodeId)
+    }
+
+    /// Iterate all edges in the order they were added.
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, &Edge<E>)> {
+        self.edges.iter().enumerate().map(|(i, e)| (EdgeId(i), e))
+    }
+
+    /// Iterate the nodes reachable from `node` along one edge, and the edge.
+    pub fn neighbors(&self, node: NodeId) -> impl Iterator<Item = (NodeId, EdgeId)> + '_ {
+        self.out[node.0].iter().copied()
+    }
+
+    /// Iterate the nodes from which `node` can be reached along one edge, and the edge.
+    ///
+    /// In an undirected graph, these are the same as the neighbors.
+    pub fn predecessors(&self, node: NodeId) -> impl Iterator<Item = (NodeId, EdgeId)> + '_ {
+        if self.directed {
+            self.incoming[node.0].iter().copied()
+        } else {
+            self.out[node.0].iter().copied()
+        }
+    }
+
+    /// Order the nodes of a directed graph so that every edge goes from an earlier node to
+    /// a later node.
+    ///
+    /// When several nodes could come next, the one whose name sorts first is chosen.
+    ///
+    /// Returns None if there is a cycle, so that no such order exists.
+    pub fn toposort(&self) -> Option<Vec<NodeId>> {
+        assert!(self.directed, "toposort needs a directed graph");
+        let mut unmet: Vec<usize> = self.incoming.iter().map(Vec::len).collect();
+        let mut ready: BTreeSet<(&str, NodeId)> = self
+            .nodes()
+            .filter(|n| unmet[n.0] == 0)
+            .map(|n| (self.name(n), n))
+            .collect();
+        let mut order = Vec::with_capacity(self.node_count());
+        while let Some((_, n)) = ready.pop_first() {
+            order.push(n);
+            for (m, _) in self.neighbors(n) {
+                unmet[m.0] -= 1;
+                if unmet[m.0] == 0 {
+                    ready.insert((self.name(m), m));
+                }
+            }
+        }
+        (order.len() == self.node_count()).then_some(order)
+    }
+
+    /// Find the strongly connected components: the largest groups of nodes
+    /// where every node can reach every other.
+    ///
+    /// In an undirected graph these are just the connected components.
+    ///
+    /// Components are returned in reverse topological order: no component
+    /// has an edge to a component later in the list.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        // Tarjan's algorithm, with an explicit stack of nodes being visited and
+        // how many of their neighbors have been examined.
+        let n = self.node_count();
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+        for root in self.nodes() {
+            if index[root.0].is_some() {
+                continue;
+            }
+            let mut visiting = vec![(root, 0)];
+            index[root.0] = Some(next_index);
+            low[root.0] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root.0] = true;
+            while let Some((u, i)) = visiting.last_mut() {
+                let u = *u;
+                if let Some(&(v, _)) = self.out[u.0].get(*i) {
+                    *i += 1;
+                    match index[v.0] {
+                        None => {
+                            index[v.0] = Some(next_index);
+                            low[v.0] = next_index;
+                            next_index += 1;
+                            stack.push(v);
+                            on_stack[v.0] = true;
+                            visiting.push((v, 0));
+                        }
+                        Some(iv) if on_stack[v.0] => low[u.0] = min(low[u.0], iv),
+                        Some(_) => (),
+                    }
+                    continue;
+                }
+                visiting.pop();
+                if let Some(&(parent, _)) = visiting.last() {
+                    low[parent.0] = min(low[parent.0], low[u.0]);
+                }
+                if Some(low[u.0]) == index[u.0] {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack[w.0] = false;
+                        component.push(w);
+                        if w == u {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Find the edges of a minimum spanning tree (or forest, if the graph
+    /// is not connected) using Kruskal's algorithm.
+    ///
+    /// Edges are treated as undirected. They are returned in the order they
+    /// were chosen, which is in increasing order of weight, with ties broken
+    /// by the order the edges were added.
+    pub fn minimum_spanning_tree(&self) -> Vec<EdgeId>
+    where
+        E: Ord,
+    {
+        let mut by_weight: Vec<EdgeId> = (0..self.edges.len()).map(EdgeId).collect();
+        by_weight.sort_by(|a, b| self.edge(*a).weight.cmp(&self.edge(*b).weight));
+        let mut components = UnionFind::new(self.node_count());
+        by_weight
+            .into_iter()
+            .filter(|e| {
+                let edge = self.edge(*e);
+                components.union(edge.from.0, edge.to.0)
+            })
+            .take(self.node_count().saturating_sub(1))
+            .collect()
+    }
+
+    /// Find the articulation points of an undirected graph: nodes whose
+    /// removal would split their component into more pieces.
+    ///
+    /// The result is sorted.
+    pub fn articulation_points(&self) -> Vec<NodeId> {
+        self.low_links().0
+    }
+
+    /// Find the bridges of an undirected graph: edges whose removal would
+    /// split their component into two pieces.
+    ///
+    /// The result is sorted.
+    pub fn bridges(&self) -> Vec<EdgeId> {
+        self.low_links().1
+    }
+
+    /// Find articulation points and bridges together, by comparing the
+    /// depth-first discovery order of each node to the earliest node reachable
+    /// from its subtree without going back through the edge it was reached by.
+    fn low_links(&self) -> (Vec<NodeId>, Vec<EdgeId>) {
+        assert!(!self.directed, "needs an undirected graph");
+        let n = self.node_count();
+        let mut disc: Vec<Option<usize>> = vec![None; n];
+        let mut low = vec![0; n];
+        let mut timer = 0;
+        let mut is_articulation = vec![false; n];
+        let mut bridges = Vec::new();
+        for root in self.nodes() {
+            if disc[root.0].is_some() {
+                continue;
+            }
+            disc[root.0] = Some(timer);
+            low[root.0] = timer;
+            timer += 1;
+            let mut root_children = 0;
+            // Nodes being visited, the edge they were reached by, and how many
+            // of their neighbors have been examined.
+            let mut visiting: Vec<(NodeId, Option<EdgeId>, usize)> = vec![(root, None, 0)];
+            while let Some((u, via, i)) = visiting.last_mut() {
+                let (u, via) = (*u, *via);
+                if let Some(&(v, e)) = self.out[u.0].get(*i) {
+                    *i += 1;
+                    if Some(e) == via {
+                        continue;
+                    }
+                    if let Some(dv) = disc[v.0] {
+                        low[u.0] = min(low[u.0], dv);
+                    } else {
+                        disc[v.0] = Some(timer);
+                        low[v.0] = timer;
+                        timer += 1;
+                        if u == root {
+                            root_children += 1;
+                        }
+                        visiting.push((v, Some(e), 0));
+                    }
+                    continue;
+                }
+                visiting.pop();
+                if let Some(&(parent, _, _)) = visiting.last() {
+                    low[parent.0] = min(low[parent.0], low[u.0]);
+                    let parent_disc = disc[parent.0].unwrap();
+                    if low[u.0] > parent_disc {
+                        bridges.push(via.unwrap());
+                    }
+                    if parent != root && low[u.0] >= parent_disc {
+                        is_articulation[parent.0] = true;
+                    }
+                }
+            }
+            if root_children > 1 {
+                is_articulation[root.0] = true;
+            }
+        }
+        bridges.sort_unstable();
+        let points = self.nodes().filter(|n| is_articulation[n.0]).collect();
+        (points, bridges)
+    }
+
+    /// Iterate every path from `from` to `to` that never visits a node twice.
+    pub fn simple_paths(
+        &self,
+        from: NodeId,
+        to: NodeId,
+    ) -> Paths<'_, N, E, impl FnMut(&[NodeId], NodeId) -> bool> {
+        self.paths_with(from, to, |path, next| !path.contains(&next))
+    }
+
+    /// Iterate every path from `from` to `to` that follows the rules given by `can_enter`.
+    ///
+    /// `can_enter` is called with the path so far, starting at `from`, and a
+    /// neighbor of its last node, and returns true if the path can continue
+    /// to that neighbor. It must eventually return false, or there will be
+    /// infinitely many paths.
+    ///
+    /// Paths end as soon as they reach `to`.
+    pub fn paths_with<F>(&self, from: NodeId, to: NodeId, can_enter: F) -> Paths<'_, N, E, F>
+    where
+        F: FnMut(&[NodeId], NodeId) -> bool,
+    {
+        Paths {
+            graph: self,
+            to,
+            can_enter,
+            path: vec![from],
+            pending: if from == to {
+                Vec::new()
+            } else {
+                vec![self.out[from.0].iter().rev().map(|(n, _)| *n).collect()]
+            },
+            trivial: from == to,
+        }
+    }
+}
+
+impl<N: Default, E> Graph<N, E> {
+    /// Return the node with this name, adding it with default data if it's
+    /// not already present.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        match self.find_node(name) {
+            Some(id) => id,
+            None => self.add_node(name, N::default()),
+        }
+    }
+
+    /// Add an edge between two nodes identified by name, adding the nodes if
+    /// they're not already present.
+    pub fn add_edge_named(&mut self, from: &str, to: &str, weight: E) -> EdgeId {
+        let (from, to) = (self.node(from), self.node(to));
+        self.add_edge(from, to, weight)
+    }
+}
+
+/// Iterates paths through a graph, from [Graph::paths_with].
+pub struct Paths<'g, N, E, F> {
+    graph: &'g Graph<N, E>,
+    to: NodeId,
+    can_enter: F,
+    /// The path to the node currently being explored.
+    path: Vec<NodeId>,
+    /// For each node on the path, the neighbors not yet tried, in reverse order.
+    pending: Vec<Vec<NodeId>>,
+    /// True if the path from the start to itself has yet to be returned.
+    trivial: bool,
+}
+
+impl<N, E, F> Iterator for Paths<'_, N, E, F>
+where
+    F: FnMut(&[NodeId], NodeId) -> bool,
+{
+    type Item = Vec<NodeId>;
+
+    fn next(&mut self) -> Option<Vec<NodeId>> {
+        if self.trivial {
+            self.trivial = false;
+            return Some(self.path.clone());
+        }
+        while let Some(untried) = self.pending.last_mut() {
+            let Some(next) = untried.pop() else {
+                self.pending.pop();
+                self.path.pop();
+                continue;
+            };
+            if !(self.can_enter)(&self.path, next) {
+                continue;
+            }
+            if next == self.to {
+                let mut found = self.path.clone();
+                found.push(next);
+                return Some(found);
+            }
+            self.path.push(next);
+            self.pending.push(
+                self.graph.out[next.0]
+                    .iter()
+                    .rev()
+                    .map(|(n, _)| *n)
+                    .collect(),
+            );
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Build a graph from lines like `a-b` or `a-b=3`.
+    fn parse(directed: bool, s: &str) -> Graph<(), u32> {
+        let mut g = if directed {
+            Graph::directed()
+        } else {
+            Graph::undirected()
+        };
+        for l in s.split_whitespace() {
+            let (ends, w) = l.split_once('=').unwrap_or((l, "0"));
+            let (a, b) = ends.split_once('-').unwrap();
+            g.add_edge_named(a, b, w.parse().unwrap());
+        }
+        g
+    }
+
+    fn names(g: &Graph<(), u32>, ns: &[NodeId]) -> Vec<String> {
+        ns.iter().map(|n| g.name(*n).to_owned()).collect()
+    }
+
+    #[test]
+    fn nodes_and_edges() {
+        let mut g = parse(true, "a-b=1 b-c=2");
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 2);
+        let b = g.find_node("b").unwrap();
+        assert_eq!(g.node("b"), b);
+        assert_eq!(g.find_node("z"), None);
+        assert_eq!(g.neighbors(b).count(), 1);
+        assert_eq!(g.predecessors(b).count(), 1);
+        let (e, edge) = g.edges().last().unwrap();
+        assert_eq!(g.name(edge.from), "b");
+        assert_eq!(g.edge(e).weight, 2);
+        let u = parse(false, "a-b b-c");
+        assert_eq!(u.neighbors(u.find_node("b").unwrap()).count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_node_name() {
+        let mut g = Graph::<(), ()>::directed();
+        g.add_node("a", ());
+        g.add_node("a", ());
+    }
+
+    #[test]
+    fn toposort_prefers_first_name() {
+        // The example from <https://adventofcode.com/2018/day/7>.
+        let g = parse(true, "C-A C-F A-B A-D B-E D-E F-E");
+        let order = g.toposort().unwrap();
+        assert_eq!(names(&g, &order).concat(), "CABDFE");
+        assert_eq!(parse(true, "a-b b-c c-a").toposort(), None);
+    }
+
+    #[test]
+    fn strongly_connected() {
+        let g = parse(true, "a-b b-c c-a c-d d-e e-d f-f");
+        let sccs: Vec<String> = g
+            .strongly_connected_components()
+            .iter()
+            .map(|c| names(&g, c).concat())
+            .collect();
+        assert_eq!(sccs, ["de", "abc", "f"]);
+        let u = parse(false, "a-b c-d d-e");
+        assert_eq!(u.strongly_connected_components().len(), 2);
+    }
+
+    #[test]
+    fn minimum_spanning_tree() {
+        let g = parse(false, "a-b=4 a-c=1 b-c=2 c-d=7 b-d=5 x-y=1");
+        let tree = g.minimum_spanning_tree();
+        let weights: Vec<u32> = tree.iter().map(|e| g.edge(*e).weight).collect();
+        assert_eq!(weights, [1, 1, 2, 5]);
+    }
+
+    #[test]
+    fn articulation_points_and_bridges() {
+        // Two triangles joined through a path c-d-e, plus a separate pair.
+        let g = parse(false, "a-b b-c c-a c-d d-e e-f f-g g-e x-y");
+        assert_eq!(names(&g, &g.articulation_points()), ["c", "d", "e"]);
+        let bridges: Vec<String> = g
+            .bridges()
+            .iter()
+            .map(|e| names(&g, &[g.edge(*e).from, g.edge(*e).to]).concat())
+            .collect();
+        assert_eq!(bridges, ["cd", "de", "xy"]);
+        // A doubled edge is not a bridge.
+        assert_eq!(parse(false, "a-b a-b").bridges(), []);
+    }
+
+    #[test]
+    fn paths_with_visit_rules() {
+        // The first example from <https://adventofcode.com/2021/day/12>.
+        let g = parse(false, "start-A start-b A-c A-b b-d A-end b-end");
+        let (start, end) = (g.find_node("start").unwrap(), g.find_node("end").unwrap());
+        let small = |n: NodeId| g.name(n).chars().all(|c| c.is_ascii_lowercase());
+        let paths: Vec<Vec<NodeId>> = g
+            .paths_with(start, end, |path, next| {
+                !small(next) || !path.contains(&next)
+            })
+            .collect();
+        assert_eq!(paths.len(), 10);
+        assert!(paths
+            .iter()
+            .all(|p| p[0] == start && *p.last().unwrap() == end));
+        assert_eq!(g.simple_paths(start, end).count(), 4);
+        assert_eq!(g.simple_paths(start, start).count(), 1);
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2026 Martin Pool
+
+//! Maximum flow, minimum cut, and bipartite matching.
+
+use std::collections::VecDeque;
+
+use super::{EdgeId, Graph, NodeId};
+
+/// The result of [Graph::max_flow].
+#[derive(Clone, Debug)]
+pub struct MaxFlow {
+    value: u64,
+    /// The flow along each edge, from its `from` end to its `to` end.
+    /// Negative in an undirected graph if it flows the other way.
+    flows: Vec<i64>,
+    /// True for nodes still reachable from the source when the flow is at its
+    /// maximum.
+    source_side: Vec<bool>,
+    cut: Vec<EdgeId>,
+}
+
+impl MaxFlow {
+    /// The total flow from the source to the sink, which is also the total
+    /// capacity of the minimum cut.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The flow along an edge, from its `from` end to its `to` end.
+    ///
+    /// In an undirected graph this is negative if the flow goes the other way.
+    pub fn flow(&self, edge: EdgeId) -> i64 {
+        self.flows[edge.0]
+    }
+
+    /// The edges of a minimum cut: removing them disconnects the sink from
+    /// the source, and their capacities add up to [MaxFlow::value].
+    ///
+    /// The result is sorted.
+    pub fn cut_edges(&self) -> &[EdgeId] {
+        &self.cut
+    }
+
+    /// True if `node` is on the same side of the minimum cut as the source.
+    pub fn is_source_side(&self, node: NodeId) -> bool {
+        self.source_side[node.0]
+    }
+
+    /// All the nodes on the same side of the minimum cut as the source.
+    pub fn source_side(&self) -> Vec<NodeId> {
+        (0..self.source_side.len())
+            .filter(|&i| self.source_side[i])
+            .map(NodeId)
+            .collect()
+    }
+}
+
+/// A residual graph for Dinic's algorithm.
+///
+/// Every edge of the original graph becomes a pair of arcs, `2*e` going
+/// forwards and `2*e+1` backwards, so the reverse of arc `a` is `a ^ 1`.
+struct Residual {
+    /// The node each arc leads to.
+    head: Vec<usize>,
+    /// Remaining capacity on each arc.
+    cap: Vec<u64>,
+    /// The arcs leaving each node.
+    arcs: Vec<Vec<usize>>,
+    /// The distance of each node from the source in the current phase.
+    level: Vec<Option<usize>>,
+    /// For each node, how many of its arcs have been fully explored in this phase.
+    next_arc: Vec<usize>,
+}
+
+impl Residual {
+    fn build_levels(&mut self, source: usize) {
+        self.level.iter_mut().for_each(|l| *l = None);
+        self.level[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for &a in &self.arcs[u] {
+                let v = self.head[a];
+                if self.cap[a] > 0 && self.level[v].is_none() {
+                    self.level[v] = Some(self.level[u].unwrap() + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+
+    /// Push up to `limit` units of flow from `u` towards `sink` along arcs
+    /// that each go one level further from the source, returning how much was pushed.
+    fn push(&mut self, u: usize, sink: usize, limit: u64) -> u64 {
+        if u == sink {
+            return limit;
+        }
+        while self.next_arc[u] < self.arcs[u].len() {
+            let a = self.arcs[u][self.next_arc[u]];
+            let v = self.head[a];
+            if self.cap[a] > 0 && self.level[v] == self.level[u].map(|l| l + 1) {
+                let pushed = self.push(v, sink, limit.min(self.cap[a]));
+                if pushed > 0 {
+                    self.cap[a] -= pushed;
+                    self.cap[a ^ 1] += pushed;
+                    return pushed;
+                }
+            }
+            self.next_arc[u] += 1;
+        }
+        0
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Find the maximum flow from `source` to `sink`, and a minimum cut
+    /// separating them, using Dinic's algorithm.
+    ///
+    /// `capacity` gives the capacity of each edge. In an undirected graph,
+    /// edges can carry this much flow in either direction. To find the
+    /// fewest edges that disconnect two nodes, give every edge capacity 1.
+    pub fn max_flow<F>(&self, source: NodeId, sink: NodeId, capacity: F) -> MaxFlow
+    where
+        F: Fn(&E) -> u64,
+    {
+        assert_ne!(source, sink, "source and sink must be different");
+        let n = self.node_count();
+        let mut res = Residual {
+            head: Vec::with_capacity(self.edges.len() * 2),
+            cap: Vec::with_capacity(self.edges.len() * 2),
+            arcs: vec![Vec::new(); n],
+            level: vec![None; n],
+            next_arc: vec![0; n],
+        };
+        for (i, edge) in self.edges.iter().enumerate() {
+            let c = capacity(&edge.weight);
+            res.head.push(edge.to.0);
+            res.cap.push(c);
+            res.arcs[edge.from.0].push(2 * i);
+            res.head.push(edge.from.0);
+            res.cap.push(if self.directed { 0 } else { c });
+            res.arcs[edge.to.0].push(2 * i + 1);
+        }
+        let original = res.cap.clone();
+        let mut value = 0;
+        loop {
+            res.build_levels(source.0);
+            if res.level[sink.0].is_none() {
+                break;
+            }
+            res.next_arc.iter_mut().for_each(|i| *i = 0);
+            loop {
+                let pushed = res.push(source.0, sink.0, u64::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                value += pushed;
+            }
+        }
+        // After the last phase, the levels mark what's reachable from the source.
+        let source_side: Vec<bool> = res.level.iter().map(Option::is_some).collect();
+        let flows = (0..self.edges.len())
+            .map(|i| original[2 * i] as i64 - res.cap[2 * i] as i64)
+            .collect();
+        let cut = self
+            .edges()
+            .filter(|(_, e)| {
+                let (a, b) = (source_side[e.from.0], source_side[e.to.0]);
+                if self.directed {
+                    a && !b
+                } else {
+                    a != b
+                }
+            })
+            .map(|(id, _)| id)
+            .collect();
+        MaxFlow {
+            value,
+            flows,
+            source_side,
+            cut,
+        }
+    }
+
+    /// Find a maximum matching between the nodes in `left` and their
+    /// neighbors: as many pairs as possible joined by an edge, with no node
+    /// in more than one pair.
+    ///
+    /// The graph should be bipartite: no edge joins two nodes of `left`.
+    ///
+    /// Returns pairs of a node from `left` and its partner, in the order of `left`.
+    pub fn maximum_matching(&self, left: &[NodeId]) -> Vec<(NodeId, NodeId)> {
+        // Kuhn's algorithm: try to find an augmenting path from each left
+        // node in turn, possibly moving earlier matches to other partners.
+        let mut partner: Vec<Option<NodeId>> = vec![None; self.node_count()];
+        for &l in left {
+            let mut seen = vec![false; self.node_count()];
+            self.augment(l, &mut partner, &mut seen);
+        }
+        left.iter()
+            .filter_map(|&l| {
+                self.neighbors(l)
+                    .map(|(r, _)| r)
+                    .find(|r| partner[r.0] == Some(l))
+                    .map(|r| (l, r))
+            })
+            .collect()
+    }
+
+    /// Try to find a partner for `l`, returning true if the matching grew.
+    fn augment(&self, l: NodeId, partner: &mut [Option<NodeId>], seen: &mut [bool]) -> bool {
+        for (r, _) in self.neighbors(l) {
+            if seen[r.0] {
+                continue;
+            }
+            seen[r.0] = true;
+            if partner[r.0].is_none_or(|prev| self.augment(prev, partner, seen)) {
+                partner[r.0] = Some(l);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn directed_flow() {
+        // The classic example from CLRS.
+        let mut g = Graph::<(), u64>::directed();
+        for (a, b, c) in [
+            ("s", "v1", 16),
+            ("s", "v2", 13),
+            ("v2", "v1", 4),
+            ("v1", "v3", 12),
+            ("v3", "v2", 9),
+            ("v2", "v4", 14),
+            ("v4", "v3", 7),
+            ("v3", "t", 20),
+            ("v4", "t", 4),
+        ] {
+            g.add_edge_named(a, b, c);
+        }
+        let (s, t) = (g.node("s"), g.node("t"));
+        let flow = g.max_flow(s, t, |c| *c);
+        assert_eq!(flow.value(), 23);
+        let cut_capacity: u64 = flow.cut_edges().iter().map(|e| g.edge(*e).weight).sum();
+        assert_eq!(cut_capacity, 23);
+        // Flow is conserved at every node other than the source and sink.
+        for n in g.nodes().filter(|n| *n != s && *n != t) {
+            let inflow: i64 = g.predecessors(n).map(|(_, e)| flow.flow(e)).sum();
+            let outflow: i64 = g.neighbors(n).map(|(_, e)| flow.flow(e)).sum();
+            assert_eq!(inflow, outflow);
+        }
+        assert!(flow.is_source_side(s));
+        assert!(!flow.is_source_side(t));
+    }
+
+    #[test]
+    fn undirected_min_cut() {
+        // Two 5-cliques joined by three edges.
+        let mut g = Graph::<(), ()>::undirected();
+        for side in ["a", "b"] {
+            for i in 0..5 {
+                for j in (i + 1)..5 {
+                    g.add_edge_named(&format!("{side}{i}"), &format!("{side}{j}"), ());
+                }
+            }
+        }
+        for i in 0..3 {
+            g.add_edge_named(&format!("a{i}"), &format!("b{i}"), ());
+        }
+        let flow = g.max_flow(
+            g.find_node("a3").unwrap(),
+            g.find_node("b3").unwrap(),
+            |_| 1,
+        );
+        assert_eq!(flow.value(), 3);
+        assert_eq!(flow.cut_edges().len(), 3);
+        let side: Vec<&str> = flow.source_side().iter().map(|n| g.name(*n)).collect();
+        assert_eq!(side, ["a0", "a1", "a2", "a3", "a4"]);
+    }
+
+    #[test]
+    fn matching() {
+        let mut g = Graph::<(), ()>::undirected();
+        // a can only take x; b could take x or y; c could take y or z.
+        for (l, r) in [("b", "x"), ("b", "y"), ("c", "y"), ("c", "z"), ("a", "x")] {
+            g.add_edge_named(l, r, ());
+        }
+        let left: Vec<NodeId> = ["a", "b", "c"].iter().map(|n| g.node(n)).collect();
+        let pairs: Vec<String> = g
+            .maximum_matching(&left)
+            .iter()
+            .map(|(l, r)| format!("{}{}", g.name(*l), g.name(*r)))
+            .collect();
+        assert_eq!(pairs, ["ax", "by", "cz"]);
+        // With only two partners available, only two can be matched.
+        let mut g = Graph::<(), ()>::undirected();
+        for (l, r) in [("a", "x"), ("b", "x"), ("c", "y")] {
+            g.add_edge_named(l, r, ());
+        }
+        let left: Vec<NodeId> = ["a", "b", "c"].iter().map(|n| g.node(n)).collect();
+        assert_eq!(g.maximum_matching(&left).len(), 2);
+    }
+}
@@ -27,8 +27,13 @@
  * 1. It's a min-heap which is more useful here.
  * 2. It's fun to implement.
  * 3. It's a great example to try proptest.
+ *
+ * Built on it are [MaxHeap]; [KeyedHeap], which orders items by a separate
+ * priority so the items themselves need not be `Ord`; and [TopK], which
+ * keeps only the largest few of the values pushed.
  */
 
+use std::cmp::{Ordering, Reverse};
 use std::iter::FromIterator;
 
 #[derive(Debug)]
@@ -120,6 +125,17 @@ impl<T: Ord> MinHeap<T> {
             }
         }
     }
+
+    /// Remove all items from the heap, returning them smallest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.pop())
+    }
+
+    /// Consume the heap, returning all its items in ascending order.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        self.v.sort_unstable();
+        self.v
+    }
 }
 
 impl<T> MinHeap<T>
@@ -197,11 +213,289 @@ impl<T: Ord> From<Vec<T>> for MinHeap<T> {
     }
 }
 
+/// A max-heap: the same as [MinHeap] but returning the largest item first.
+#[derive(Debug)]
+pub struct MaxHeap<T: Ord> {
+    heap: MinHeap<Reverse<T>>,
+}
+
+impl<T: Ord> MaxHeap<T> {
+    /// Construct a new empty heap.
+    pub fn new() -> MaxHeap<T> {
+        MaxHeap {
+            heap: MinHeap::new(),
+        }
+    }
+
+    /// Contruct a heap with a hint to the expected capacity.
+    pub fn with_capacity(capacity: usize) -> MaxHeap<T> {
+        MaxHeap {
+            heap: MinHeap::with_capacity(capacity),
+        }
+    }
+
+    /// Return a reference to the maximum value in the heap,
+    /// if there is one, or None if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|r| &r.0)
+    }
+
+    /// Remove and return the maximum value in the heap, if there is one.
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|r| r.0)
+    }
+
+    /// Insert a new item into the heap.
+    pub fn push(&mut self, t: T) {
+        self.heap.push(Reverse(t))
+    }
+
+    /// Return the number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Discard all elements from the heap.
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    /// Remove all items from the heap, returning them largest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.heap.drain().map(|r| r.0)
+    }
+
+    /// Consume the heap, returning all its items in descending order.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|r| r.0)
+            .collect()
+    }
+}
+
+impl<T: Ord> Default for MaxHeap<T> {
+    fn default() -> Self {
+        MaxHeap::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MaxHeap<T> {
+    fn from_iter<It>(into_iter: It) -> MaxHeap<T>
+    where
+        It: IntoIterator<Item = T>,
+    {
+        MaxHeap {
+            heap: into_iter.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+/// An entry in a [KeyedHeap], compared only by its priority and then by
+/// the order it was pushed.
+#[derive(Debug)]
+struct Keyed<T, P> {
+    priority: P,
+    seq: usize,
+    item: T,
+}
+
+impl<T, P: Ord> PartialEq for Keyed<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, P: Ord> Eq for Keyed<T, P> {}
+
+impl<T, P: Ord> PartialOrd for Keyed<T, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, P: Ord> Ord for Keyed<T, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then(self.seq.cmp(&other.seq))
+    }
+}
+
+/// A min-heap of items ordered by a separate priority, so the items
+/// need not be `Ord`.
+///
+/// Items with equal priority are popped in the order they were pushed.
+///
+/// Unlike [crate::IndexedMinHeap] entries can't be found again after
+/// they're pushed, which makes this one simpler and faster.
+#[derive(Debug)]
+pub struct KeyedHeap<T, P: Ord> {
+    heap: MinHeap<Keyed<T, P>>,
+    next_seq: usize,
+}
+
+impl<T, P: Ord> KeyedHeap<T, P> {
+    /// Construct a new empty heap.
+    pub fn new() -> KeyedHeap<T, P> {
+        KeyedHeap {
+            heap: MinHeap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Return the item with the lowest priority, and its priority, without removing it.
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.heap.peek().map(|k| (&k.item, &k.priority))
+    }
+
+    /// Remove and return the item with the lowest priority, and its priority.
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        self.heap.pop().map(|k| (k.item, k.priority))
+    }
+
+    /// Insert a new item with a given priority.
+    pub fn push(&mut self, item: T, priority: P) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.heap.push(Keyed {
+            priority,
+            seq,
+            item,
+        })
+    }
+
+    /// Return the number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Discard all elements from the heap.
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    /// Remove all items from the heap, with their priorities, lowest priority first.
+    pub fn drain(&mut self) -> impl Iterator<Item = (T, P)> + '_ {
+        self.heap.drain().map(|k| (k.item, k.priority))
+    }
+
+    /// Consume the heap, returning all its items and their priorities,
+    /// lowest priority first.
+    pub fn into_sorted_vec(self) -> Vec<(T, P)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|k| (k.item, k.priority))
+            .collect()
+    }
+}
+
+impl<T, P: Ord> Default for KeyedHeap<T, P> {
+    fn default() -> Self {
+        KeyedHeap::new()
+    }
+}
+
+impl<T, P: Ord> FromIterator<(T, P)> for KeyedHeap<T, P> {
+    fn from_iter<It>(into_iter: It) -> KeyedHeap<T, P>
+    where
+        It: IntoIterator<Item = (T, P)>,
+    {
+        let mut heap = KeyedHeap::new();
+        heap.extend(into_iter);
+        heap
+    }
+}
+
+impl<T, P: Ord> Extend<(T, P)> for KeyedHeap<T, P> {
+    fn extend<It: IntoIterator<Item = (T, P)>>(&mut self, iter: It) {
+        for (item, priority) in iter {
+            self.push(item, priority)
+        }
+    }
+}
+
+/// Keeps the `k` largest of all the values pushed, for answers like "the
+/// product of the three largest".
+///
+/// This takes `O(log k)` time per value and only `O(k)` memory however
+/// many values are pushed.
+#[derive(Debug)]
+pub struct TopK<T: Ord> {
+    k: usize,
+    /// The largest values so far, with the smallest of them on top
+    /// ready to be displaced.
+    heap: MinHeap<T>,
+}
+
+impl<T: Ord> TopK<T> {
+    /// Construct an empty collection that will keep the `k` largest values.
+    pub fn new(k: usize) -> TopK<T> {
+        TopK {
+            k,
+            heap: MinHeap::with_capacity(k + 1),
+        }
+    }
+
+    /// Offer a value, which is kept if it's among the `k` largest so far.
+    pub fn push(&mut self, t: T) {
+        if self.heap.len() < self.k {
+            self.heap.push(t)
+        } else if self.heap.peek().is_some_and(|min| t > *min) {
+            self.heap.pop();
+            self.heap.push(t);
+        }
+    }
+
+    /// The number of values kept, which is at most `k`.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if no values are kept.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The smallest of the values kept, which is the `k`th largest
+    /// once at least `k` values have been pushed.
+    pub fn smallest(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Consume the collection, returning the values kept, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut v = self.heap.into_sorted_vec();
+        v.reverse();
+        v
+    }
+}
+
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        for t in iter {
+            self.push(t)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use proptest::prelude::*;
 
-    use super::{left, parent, right, MinHeap};
+    use super::{left, parent, right, KeyedHeap, MaxHeap, MinHeap, TopK};
 
     #[test]
     fn new_heap_is_empty() {
@@ -312,5 +606,58 @@ mod test {
             }
             assert!(heap.is_empty());
         }
+
+        #[test]
+        fn drain_and_into_sorted_vec(vals: Vec<isize>) {
+            let mut sorted = vals.clone();
+            sorted.sort_unstable();
+            let mut heap: MinHeap<isize> = vals.iter().copied().collect();
+            assert_eq!(heap.drain().collect::<Vec<_>>(), sorted);
+            assert!(heap.is_empty());
+            let heap: MinHeap<isize> = vals.iter().copied().collect();
+            assert_eq!(heap.into_sorted_vec(), sorted);
+            let mut max_heap: MaxHeap<isize> = vals.iter().copied().collect();
+            assert_eq!(max_heap.peek(), sorted.last());
+            sorted.reverse();
+            assert_eq!(max_heap.drain().collect::<Vec<_>>(), sorted);
+            let max_heap: MaxHeap<isize> = vals.into_iter().collect();
+            assert_eq!(max_heap.into_sorted_vec(), sorted);
+        }
+
+        #[test]
+        fn keyed_heap_is_stable_sort(vals: Vec<(u8, String)>) {
+            // The priorities are small so there are plenty of ties.
+            let heap: KeyedHeap<String, u8> = vals.iter().map(|(p, s)| (s.clone(), *p)).collect();
+            assert_eq!(heap.len(), vals.len());
+            let mut expected: Vec<(String, u8)> = vals.into_iter().map(|(p, s)| (s, p)).collect();
+            expected.sort_by_key(|(_, p)| *p);
+            assert_eq!(heap.into_sorted_vec(), expected);
+        }
+
+        #[test]
+        fn top_k_agrees_with_sort(k in 0..10usize, vals: Vec<i32>) {
+            let mut top = TopK::new(k);
+            top.extend(vals.iter().copied());
+            let mut sorted = vals;
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            sorted.truncate(k);
+            assert_eq!(top.len(), sorted.len());
+            assert_eq!(top.smallest(), sorted.last());
+            assert_eq!(top.into_sorted_vec(), sorted);
+        }
+    }
+
+    #[test]
+    fn keyed_heap_payload_needs_no_ordering() {
+        // f64 is not Ord.
+        let mut heap = KeyedHeap::new();
+        heap.push(2.5, 'b');
+        heap.push(f64::NAN, 'c');
+        heap.push(-1.0, 'a');
+        assert_eq!(heap.peek(), Some((&-1.0, &'a')));
+        assert_eq!(heap.pop(), Some((-1.0, 'a')));
+        let rest: Vec<char> = heap.drain().map(|(_, p)| p).collect();
+        assert_eq!(rest, ['b', 'c']);
+        assert!(heap.is_empty());
     }
 }
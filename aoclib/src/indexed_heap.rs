@@ -0,0 +1,323 @@
+// Copyright 2026 Martin Pool
+
+//! A min-heap whose entries can be found again after they're pushed, to
+//! change their priority or remove them.
+//!
+//! Like [crate::MinHeap] this is a binary heap packed into a vec, but the vec
+//! holds handles rather than the entries themselves, and every entry
+//! remembers its position in the vec so that it can be moved up or down
+//! when its priority changes.
+
+use std::cmp::Ordering;
+
+/// Identifies an entry pushed onto an [IndexedMinHeap].
+///
+/// Handles are never reused within one heap, so a handle for an entry that
+/// has been popped or removed stays invalid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HeapHandle(usize);
+
+#[derive(Debug)]
+struct Slot<T, P> {
+    item: T,
+    priority: P,
+    /// The position of this entry's handle in the heap vec.
+    pos: usize,
+}
+
+/// A min-heap of items ordered by a separate priority, supporting
+/// [IndexedMinHeap::decrease_key] and removal of arbitrary entries.
+///
+/// Entries with equal priority are popped in the order they were pushed.
+#[derive(Debug)]
+pub struct IndexedMinHeap<T, P: Ord> {
+    /// Handles, arranged as a binary heap by priority.
+    heap: Vec<usize>,
+    /// The entry for each handle, or None once it's been popped or removed.
+    slots: Vec<Option<Slot<T, P>>>,
+}
+
+impl<T, P: Ord> Default for IndexedMinHeap<T, P> {
+    fn default() -> Self {
+        IndexedMinHeap::new()
+    }
+}
+
+impl<T, P: Ord> IndexedMinHeap<T, P> {
+    pub fn new() -> Self {
+        IndexedMinHeap {
+            heap: Vec::new(),
+            slots: Vec::new(),
+        }
+    }
+
+    /// Return the number of entries in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Insert a new item, and return a handle that can be used to find it again.
+    pub fn push(&mut self, item: T, priority: P) -> HeapHandle {
+        let h = self.slots.len();
+        let pos = self.heap.len();
+        self.slots.push(Some(Slot {
+            item,
+            priority,
+            pos,
+        }));
+        self.heap.push(h);
+        self.sift_up(pos);
+        HeapHandle(h)
+    }
+
+    /// Return the item with the lowest priority, and its priority, without removing it.
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.heap.first().map(|&h| {
+            let slot = self.slot(h);
+            (&slot.item, &slot.priority)
+        })
+    }
+
+    /// Remove and return the item with the lowest priority, and its priority.
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        let &h = self.heap.first()?;
+        self.remove(HeapHandle(h))
+    }
+
+    /// True if the entry for this handle is still in the heap.
+    pub fn contains(&self, handle: HeapHandle) -> bool {
+        self.slots.get(handle.0).is_some_and(Option::is_some)
+    }
+
+    /// Return the item for this handle, if it's still in the heap.
+    pub fn get(&self, handle: HeapHandle) -> Option<&T> {
+        self.slots.get(handle.0)?.as_ref().map(|s| &s.item)
+    }
+
+    /// Return the priority of this handle, if it's still in the heap.
+    pub fn priority(&self, handle: HeapHandle) -> Option<&P> {
+        self.slots.get(handle.0)?.as_ref().map(|s| &s.priority)
+    }
+
+    /// Remove an entry from anywhere in the heap, returning its item and
+    /// priority, or None if it was already popped or removed.
+    pub fn remove(&mut self, handle: HeapHandle) -> Option<(T, P)> {
+        let pos = self.slots.get(handle.0)?.as_ref()?.pos;
+        let last = self.heap.len() - 1;
+        self.swap(pos, last);
+        self.heap.pop();
+        if pos < self.heap.len() {
+            // The entry moved into pos might belong either higher or lower.
+            self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        let slot = self.slots[handle.0].take().unwrap();
+        Some((slot.item, slot.priority))
+    }
+
+    /// Change the priority of an entry, moving it up or down the heap.
+    ///
+    /// Panics if the entry is no longer in the heap.
+    pub fn change_priority(&mut self, handle: HeapHandle, priority: P) {
+        let slot = self.slots[handle.0]
+            .as_mut()
+            .expect("handle is not in the heap");
+        let pos = slot.pos;
+        let lower = priority < slot.priority;
+        slot.priority = priority;
+        if lower {
+            self.sift_up(pos);
+        } else {
+            self.sift_down(pos);
+        }
+    }
+
+    /// Lower the priority of an entry, if `priority` is less than its
+    /// current priority, and return true if it changed.
+    ///
+    /// Panics if the entry is no longer in the heap.
+    pub fn decrease_key(&mut self, handle: HeapHandle, priority: P) -> bool {
+        let current = self.priority(handle).expect("handle is not in the heap");
+        if priority < *current {
+            self.change_priority(handle, priority);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove all entries.
+    ///
+    /// Handles from before the heap was cleared remain invalid.
+    pub fn clear(&mut self) {
+        for &h in &self.heap {
+            self.slots[h] = None;
+        }
+        self.heap.clear();
+    }
+
+    fn slot(&self, h: usize) -> &Slot<T, P> {
+        self.slots[h].as_ref().unwrap()
+    }
+
+    /// Compare the entries at two positions of the heap vec, breaking ties
+    /// by the order they were pushed.
+    fn cmp_at(&self, i: usize, j: usize) -> Ordering {
+        let (hi, hj) = (self.heap[i], self.heap[j]);
+        self.slot(hi)
+            .priority
+            .cmp(&self.slot(hj).priority)
+            .then(hi.cmp(&hj))
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        let (hi, hj) = (self.heap[i], self.heap[j]);
+        self.slots[hi].as_mut().unwrap().pos = i;
+        self.slots[hj].as_mut().unwrap().pos = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let p = (i - 1) / 2;
+            if self.cmp_at(i, p) == Ordering::Less {
+                self.swap(i, p);
+                i = p;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (l, r) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+            if l < self.heap.len() && self.cmp_at(l, smallest) == Ordering::Less {
+                smallest = l;
+            }
+            if r < self.heap.len() && self.cmp_at(r, smallest) == Ordering::Less {
+                smallest = r;
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+
+    /// Check that the heap invariants are true.
+    ///
+    /// Panics on failure.
+    pub fn assert_valid(&self) {
+        for (i, &h) in self.heap.iter().enumerate() {
+            assert_eq!(self.slot(h).pos, i, "handle {h} has wrong position");
+            if i > 0 {
+                assert_ne!(self.cmp_at(i, (i - 1) / 2), Ordering::Less);
+            }
+        }
+        assert_eq!(
+            self.slots.iter().filter(|s| s.is_some()).count(),
+            self.heap.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn decrease_key_reorders() {
+        let mut heap = IndexedMinHeap::new();
+        let a = heap.push("a", 10);
+        let b = heap.push("b", 20);
+        let c = heap.push("c", 30);
+        assert!(heap.decrease_key(c, 5));
+        assert!(!heap.decrease_key(a, 15));
+        assert_eq!(heap.peek(), Some((&"c", &5)));
+        heap.change_priority(c, 25);
+        assert_eq!(heap.remove(b), Some(("b", 20)));
+        assert!(!heap.contains(b));
+        assert_eq!(heap.remove(b), None);
+        assert_eq!(heap.get(c), Some(&"c"));
+        assert_eq!(heap.priority(a), Some(&10));
+        assert_eq!(heap.pop(), Some(("a", 10)));
+        assert_eq!(heap.pop(), Some(("c", 25)));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn ties_pop_in_push_order() {
+        let mut heap = IndexedMinHeap::new();
+        for c in ['x', 'y', 'z'] {
+            heap.push(c, 1);
+        }
+        let order: Vec<char> = std::iter::from_fn(|| heap.pop().map(|(c, _)| c)).collect();
+        assert_eq!(order, ['x', 'y', 'z']);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(i32),
+        Pop,
+        Remove(usize),
+        Change(usize, i32),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            any::<i32>().prop_map(Op::Push),
+            Just(Op::Pop),
+            any::<usize>().prop_map(Op::Remove),
+            (any::<usize>(), any::<i32>()).prop_map(|(i, p)| Op::Change(i, p)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_model(ops in prop::collection::vec(op(), 0..100)) {
+            let mut heap = IndexedMinHeap::new();
+            // The model is the priority, handle and item of every live entry,
+            // which sorts in the same order as the heap.
+            let mut model: Vec<(i32, HeapHandle, i32)> = Vec::new();
+            for op in ops {
+                match op {
+                    Op::Push(p) => {
+                        let h = heap.push(p, p);
+                        model.push((p, h, p));
+                    }
+                    Op::Pop => {
+                        model.sort_unstable();
+                        let expected = (!model.is_empty()).then(|| {
+                            let (p, _, item) = model.remove(0);
+                            (item, p)
+                        });
+                        prop_assert_eq!(heap.pop(), expected);
+                    }
+                    Op::Remove(i) if !model.is_empty() => {
+                        let (p, h, item) = model.remove(i % model.len());
+                        prop_assert_eq!(heap.remove(h), Some((item, p)));
+                    }
+                    Op::Change(i, p) if !model.is_empty() => {
+                        let i = i % model.len();
+                        heap.change_priority(model[i].1, p);
+                        model[i].0 = p;
+                    }
+                    _ => (),
+                }
+                heap.assert_valid();
+                prop_assert_eq!(heap.len(), model.len());
+            }
+        }
+    }
+}
@@ -12,16 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bigint;
+mod cuboid;
+pub mod cycle;
+pub mod graph;
 mod heap;
+mod indexed_heap;
 pub mod input;
+pub mod line;
 mod matrix;
+mod memo;
+mod modint;
+pub mod numtheory;
 pub mod permute;
 mod point;
+mod radix_heap;
 pub mod range;
+mod rational;
+pub mod sequence;
 pub mod shortest_path;
 mod split_one;
+mod union_find;
 
-pub use heap::MinHeap;
+pub use bigint::BigInt;
+pub use cuboid::{Cuboid, CuboidSet};
+pub use heap::{KeyedHeap, MaxHeap, MinHeap, TopK};
+pub use indexed_heap::{HeapHandle, IndexedMinHeap};
 pub use matrix::*;
+pub use memo::{Memo, MemoStats};
+pub use modint::{Affine, DynModInt, ModInt, Modular};
 pub use point::*;
+pub use radix_heap::RadixHeap;
+pub use rational::Rational;
 pub use split_one::{split_one, try_split_one};
+pub use union_find::{KeyedUnionFind, UnionFind};
@@ -0,0 +1,328 @@
+// Copyright 2026 Martin Pool
+
+//! Straight lines between integer points.
+
+use std::cmp::{max, min};
+
+use crate::{point, Point};
+
+/// A straight line segment between two points, including both ends.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub a: Point,
+    pub b: Point,
+}
+
+/// How two segments meet, from [Segment::intersection].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Intersection {
+    /// The segments don't touch.
+    None,
+    /// The segments cross or touch at exactly one integer point.
+    Point(Point),
+    /// The segments cross at exactly one point, which doesn't have integer
+    /// coordinates.
+    Fractional,
+    /// The segments are collinear and share this run of more than one point.
+    Overlap(Segment),
+}
+
+impl Segment {
+    pub fn new(a: Point, b: Point) -> Segment {
+        Segment { a, b }
+    }
+
+    /// True if the segment is horizontal or vertical (or a single point).
+    pub fn is_axis_aligned(&self) -> bool {
+        self.a.x == self.b.x || self.a.y == self.b.y
+    }
+
+    /// True if the segment runs at exactly 45 degrees.
+    pub fn is_diagonal(&self) -> bool {
+        self.a != self.b && (self.b.x - self.a.x).abs() == (self.b.y - self.a.y).abs()
+    }
+
+    /// Iterate the points on the segment from `a` to `b`, including both.
+    ///
+    /// Horizontal, vertical and 45 degree lines include exactly the points
+    /// on the line; others are approximated by [Bresenham].
+    pub fn points(&self) -> Bresenham {
+        Bresenham::new(self.a, self.b)
+    }
+
+    /// True if `p` lies exactly on this segment.
+    pub fn contains(&self, p: Point) -> bool {
+        (self.b - self.a).cross(&(p - self.a)) == 0
+            && min(self.a.x, self.b.x) <= p.x
+            && p.x <= max(self.a.x, self.b.x)
+            && min(self.a.y, self.b.y) <= p.y
+            && p.y <= max(self.a.y, self.b.y)
+    }
+
+    /// Find where this segment meets another, treating both as continuous
+    /// lines between their endpoints.
+    pub fn intersection(&self, other: &Segment) -> Intersection {
+        if self.a == self.b {
+            return if other.contains(self.a) {
+                Intersection::Point(self.a)
+            } else {
+                Intersection::None
+            };
+        } else if other.a == other.b {
+            return other.intersection(self);
+        }
+        let d1 = self.b - self.a;
+        let d2 = other.b - other.a;
+        let ac = other.a - self.a;
+        let denom = d1.cross(&d2);
+        if denom == 0 {
+            if ac.cross(&d1) != 0 {
+                // Parallel, but not on the same line.
+                return Intersection::None;
+            }
+            // Collinear: order all the endpoints by their position along self.
+            let key = |p: &Point| (*p - self.a).dot(&d1);
+            let sort = |p: Point, q: Point| if key(&p) <= key(&q) { (p, q) } else { (q, p) };
+            let (s0, s1) = sort(self.a, self.b);
+            let (o0, o1) = sort(other.a, other.b);
+            let lo = if key(&s0) >= key(&o0) { s0 } else { o0 };
+            let hi = if key(&s1) <= key(&o1) { s1 } else { o1 };
+            return match key(&lo).cmp(&key(&hi)) {
+                std::cmp::Ordering::Greater => Intersection::None,
+                std::cmp::Ordering::Equal => Intersection::Point(lo),
+                std::cmp::Ordering::Less => Intersection::Overlap(Segment::new(lo, hi)),
+            };
+        }
+        // Solve self.a + t * d1 == other.a + u * d2, with t and u as fractions over denom.
+        let (mut t, mut u, mut denom) = (ac.cross(&d2), ac.cross(&d1), denom);
+        if denom < 0 {
+            (t, u, denom) = (-t, -u, -denom);
+        }
+        if !(0..=denom).contains(&t) || !(0..=denom).contains(&u) {
+            return Intersection::None;
+        }
+        let (nx, ny) = (d1.x * t, d1.y * t);
+        if nx % denom == 0 && ny % denom == 0 {
+            Intersection::Point(self.a + point(nx / denom, ny / denom))
+        } else {
+            Intersection::Fractional
+        }
+    }
+}
+
+/// Iterates the points on a line between two points, using
+/// [Bresenham's algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm).
+///
+/// Each point after the first is one of the 8-way neighbors of the previous point.
+#[derive(Clone, Debug)]
+pub struct Bresenham {
+    p: Point,
+    dx: isize,
+    dy: isize,
+    step: Point,
+    err: isize,
+    remaining: usize,
+}
+
+impl Bresenham {
+    /// Start a line from `from` to `to`, including both.
+    pub fn new(from: Point, to: Point) -> Bresenham {
+        let dx = (to.x - from.x).abs();
+        let dy = -(to.y - from.y).abs();
+        Bresenham {
+            p: from,
+            dx,
+            dy,
+            step: (to - from).signum(),
+            err: dx + dy,
+            remaining: from.chebyshev_distance(&to) as usize + 1,
+        }
+    }
+}
+
+impl Iterator for Bresenham {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let r = self.p;
+        if self.remaining > 0 {
+            let e2 = 2 * self.err;
+            if e2 >= self.dy {
+                self.err += self.dy;
+                self.p.x += self.step.x;
+            }
+            if e2 <= self.dx {
+                self.err += self.dx;
+                self.p.y += self.step.y;
+            }
+        }
+        Some(r)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Bresenham {}
+
+/// Iterate all the points along a path of straight lines joining the given
+/// vertices in order.
+///
+/// Each vertex is produced only once, even though it ends one segment and
+/// begins the next.
+pub fn polyline(vertices: &[Point]) -> impl Iterator<Item = Point> + '_ {
+    vertices.first().copied().into_iter().chain(
+        vertices
+            .windows(2)
+            .flat_map(|w| Bresenham::new(w[0], w[1]).skip(1)),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn seg(ax: isize, ay: isize, bx: isize, by: isize) -> Segment {
+        Segment::new(point(ax, ay), point(bx, by))
+    }
+
+    #[test]
+    fn axis_and_diagonal_lines() {
+        assert_eq!(
+            seg(2, 1, 2, 3).points().collect::<Vec<_>>(),
+            [point(2, 1), point(2, 2), point(2, 3)]
+        );
+        assert_eq!(
+            seg(3, 0, 0, 3).points().collect::<Vec<_>>(),
+            [point(3, 0), point(2, 1), point(1, 2), point(0, 3)]
+        );
+        assert!(seg(3, 0, 0, 3).is_diagonal());
+        assert!(!seg(3, 0, 0, 2).is_diagonal());
+        assert!(seg(3, 0, 0, 0).is_axis_aligned());
+        assert_eq!(seg(5, 5, 5, 5).points().collect::<Vec<_>>(), [point(5, 5)]);
+    }
+
+    #[test]
+    fn shallow_line() {
+        assert_eq!(
+            seg(0, 0, 4, 2).points().collect::<Vec<_>>(),
+            [
+                point(0, 0),
+                point(1, 1),
+                point(2, 1),
+                point(3, 2),
+                point(4, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn polyline_shares_vertices() {
+        // The first rock path from <https://adventofcode.com/2022/day/14>.
+        let path = [point(498, 4), point(498, 6), point(496, 6)];
+        assert_eq!(
+            polyline(&path).collect::<Vec<_>>(),
+            [
+                point(498, 4),
+                point(498, 5),
+                point(498, 6),
+                point(497, 6),
+                point(496, 6)
+            ]
+        );
+        assert_eq!(polyline(&[]).count(), 0);
+        assert_eq!(polyline(&[point(1, 1)]).count(), 1);
+    }
+
+    #[test]
+    fn crossing_intersections() {
+        assert_eq!(
+            seg(0, 0, 4, 4).intersection(&seg(0, 4, 4, 0)),
+            Intersection::Point(point(2, 2))
+        );
+        assert_eq!(
+            seg(0, 0, 3, 3).intersection(&seg(0, 3, 3, 0)),
+            Intersection::Fractional
+        );
+        assert_eq!(
+            seg(0, 0, 4, 0).intersection(&seg(4, 0, 4, 9)),
+            Intersection::Point(point(4, 0))
+        );
+        assert_eq!(
+            seg(0, 0, 4, 0).intersection(&seg(5, -1, 5, 9)),
+            Intersection::None
+        );
+        assert_eq!(
+            seg(0, 0, 4, 0).intersection(&seg(0, 1, 4, 1)),
+            Intersection::None
+        );
+    }
+
+    #[test]
+    fn collinear_intersections() {
+        assert_eq!(
+            seg(0, 0, 6, 0).intersection(&seg(8, 0, 4, 0)),
+            Intersection::Overlap(seg(4, 0, 6, 0))
+        );
+        assert_eq!(
+            seg(1, 1, 5, 5).intersection(&seg(0, 0, 9, 9)),
+            Intersection::Overlap(seg(1, 1, 5, 5))
+        );
+        assert_eq!(
+            seg(0, 0, 2, 0).intersection(&seg(2, 0, 3, 0)),
+            Intersection::Point(point(2, 0))
+        );
+        assert_eq!(
+            seg(0, 0, 2, 0).intersection(&seg(3, 0, 5, 0)),
+            Intersection::None
+        );
+        assert_eq!(
+            seg(3, 3, 3, 3).intersection(&seg(0, 0, 9, 9)),
+            Intersection::Point(point(3, 3))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn bresenham_steps_between_neighbors(
+            ax in -20isize..20, ay in -20isize..20, bx in -20isize..20, by in -20isize..20)
+        {
+            let (a, b) = (point(ax, ay), point(bx, by));
+            let ps: Vec<Point> = Bresenham::new(a, b).collect();
+            prop_assert_eq!(ps.len() as isize, a.chebyshev_distance(&b) + 1);
+            prop_assert_eq!(ps[0], a);
+            prop_assert_eq!(*ps.last().unwrap(), b);
+            for w in ps.windows(2) {
+                prop_assert_eq!(w[0].chebyshev_distance(&w[1]), 1);
+            }
+        }
+
+        #[test]
+        fn intersection_points_are_on_both_segments(
+            c in prop::array::uniform8(-6isize..6))
+        {
+            let s = seg(c[0], c[1], c[2], c[3]);
+            let t = seg(c[4], c[5], c[6], c[7]);
+            let shared: Vec<Point> = (-6..6)
+                .flat_map(|y| (-6..6).map(move |x| point(x, y)))
+                .filter(|p| s.contains(*p) && t.contains(*p))
+                .collect();
+            match s.intersection(&t) {
+                Intersection::None | Intersection::Fractional => prop_assert!(shared.is_empty()),
+                Intersection::Point(p) => prop_assert_eq!(shared, vec![p]),
+                Intersection::Overlap(o) => {
+                    prop_assert!(s.contains(o.a) && s.contains(o.b));
+                    prop_assert!(t.contains(o.a) && t.contains(o.b));
+                    prop_assert!(shared.iter().all(|p| o.contains(*p)));
+                }
+            }
+        }
+    }
+}
@@ -19,14 +19,16 @@
 
 use std::borrow::Borrow;
 use std::cmp::max;
+use std::collections::HashMap;
 use std::fmt;
 use std::iter::{repeat, FromIterator};
 use std::ops::{Index, IndexMut};
 
-use crate::shortest_path::ShortestPath;
+use crate::shortest_path::bfs::{search_01, DenseTable};
+use crate::shortest_path::{pairwise_distances, DistanceMap, ShortestPath};
 use crate::{point, Dir, Point};
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct Matrix<T> {
     w: usize,
     h: usize,
@@ -299,7 +301,7 @@ impl<T> Matrix<T> {
     ///
     /// Only moves in the four cardinal directions are permitted.
     ///
-    /// All permitted moves have unit cost.
+    /// All permitted moves have unit cost, so this uses breadth-first search.
     pub fn shortest_path<F>(
         &self,
         start: Point,
@@ -309,8 +311,8 @@ impl<T> Matrix<T> {
     where
         F: Fn(&T, &T) -> bool,
     {
-        ShortestPath::find(
-            &start,
+        search_01(
+            [start],
             |p| *p == end,
             |p| {
                 self.neighbors4(*p)
@@ -318,8 +320,110 @@ impl<T> Matrix<T> {
                     .map(|(q, _c)| (q, 1))
                     .collect::<Vec<(Point, usize)>>()
             },
+            DenseTable::new(self.w, self.h),
+            &mut (),
         )
     }
+
+    /// Find the shortest path to `end` from whichever of several starting
+    /// points is nearest, as for [Matrix::shortest_path].
+    ///
+    /// The winning start is the [ShortestPath::origin] of the result.
+    pub fn shortest_path_from_any<S, F>(
+        &self,
+        starts: S,
+        end: Point,
+        can_move: F,
+    ) -> Option<ShortestPath<Point, usize>>
+    where
+        S: IntoIterator<Item = Point>,
+        F: Fn(&T, &T) -> bool,
+    {
+        search_01(
+            starts,
+            |p| *p == end,
+            |p| {
+                self.neighbors4(*p)
+                    .filter(|(_q, c)| can_move(&self[*p], *c))
+                    .map(|(q, _c)| (q, 1))
+                    .collect::<Vec<(Point, usize)>>()
+            },
+            DenseTable::new(self.w, self.h),
+            &mut (),
+        )
+    }
+
+    /// Find the shortest path from one point in a matrix to another, where
+    /// each move costs either 0 or 1.
+    ///
+    /// `step_cost` is called with the values of the current cell and of a
+    /// neighbor, and returns the cost to move there, or None if the move
+    /// is not possible.
+    ///
+    /// Only moves in the four cardinal directions are permitted.
+    pub fn shortest_path_01<F>(
+        &self,
+        start: Point,
+        end: Point,
+        step_cost: F,
+    ) -> Option<ShortestPath<Point, usize>>
+    where
+        F: Fn(&T, &T) -> Option<usize>,
+    {
+        search_01(
+            [start],
+            |p| *p == end,
+            |p| {
+                self.neighbors4(*p)
+                    .filter_map(|(q, c)| step_cost(&self[*p], c).map(|cost| (q, cost)))
+                    .collect::<Vec<(Point, usize)>>()
+            },
+            DenseTable::new(self.w, self.h),
+            &mut (),
+        )
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Find the number of steps from `start` to every point reachable from
+    /// it, moving in the four cardinal directions where `can_move` allows.
+    pub fn distances_from<F>(&self, start: Point, can_move: F) -> DistanceMap<Point, usize>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        DistanceMap::bfs(&start, |p| self.moves4(*p, &can_move))
+    }
+
+    /// Find the number of steps between every pair of some points of interest,
+    /// moving in the four cardinal directions where `can_move` allows.
+    ///
+    /// This can be used to build a smaller graph of just those points.
+    pub fn pairwise_distances<F>(
+        &self,
+        points: &[Point],
+        can_move: F,
+    ) -> HashMap<(Point, Point), usize>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        pairwise_distances(points, |p| {
+            self.moves4(*p, &can_move)
+                .into_iter()
+                .map(|q| (q, 1))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Return the 4-way neighbors that can be reached in one step from `p`.
+    fn moves4<F>(&self, p: Point, can_move: &F) -> Vec<Point>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        self.neighbors4(p)
+            .filter(|(_q, c)| can_move(&self[p], c))
+            .map(|(q, _c)| q)
+            .collect()
+    }
 }
 
 impl<T: Clone> Matrix<T> {
@@ -332,6 +436,19 @@ impl<T: Clone> Matrix<T> {
         }
     }
 
+    /// Make a builder that will accumulate rows of a matrix.
+    pub fn from_rows() -> FromRows<T> {
+        FromRows {
+            w: 0,
+            d: Vec::new(),
+        }
+    }
+
+    /// Return a vec of the values of all present 8-way neighbors.
+    pub fn neighbor8_values(&self, p: Point) -> Vec<T> {
+        self.neighbors8(p).map(|(_q, v)| v.clone()).collect()
+    }
+
     /// Make a new matrix the same size as `m` with a default value `fill`.
     pub fn same_size<Q>(m: &Matrix<Q>, fill: T) -> Matrix<T> {
         Matrix::new(m.width(), m.height(), fill)
@@ -531,6 +648,37 @@ impl<T> IndexMut<Point> for Matrix<T> {
     }
 }
 
+/// Accumulates rows to build a [Matrix], from [Matrix::from_rows].
+pub struct FromRows<T> {
+    w: usize,
+    d: Vec<T>,
+}
+
+impl<T: Clone> FromRows<T> {
+    /// Add a row below those already added.
+    ///
+    /// Panics if the row is not the same length as the first row.
+    pub fn add_row(&mut self, r: &[T]) {
+        if self.d.is_empty() {
+            assert!(!r.is_empty());
+            self.w = r.len();
+        } else {
+            assert_eq!(r.len(), self.w, "Rows must be the same length");
+        }
+        self.d.extend_from_slice(r);
+    }
+
+    pub fn finish(mut self) -> Matrix<T> {
+        self.d.shrink_to_fit();
+        let h = self.d.len().checked_div(self.w).unwrap_or(0);
+        Matrix {
+            w: self.w,
+            h,
+            d: self.d,
+        }
+    }
+}
+
 /// Build a Matrix from an iterator of rows, each item of which is an iterator of cells.
 ///
 /// All the rows must be the same length.
@@ -0,0 +1,171 @@
+// Copyright 2026 Martin Pool
+
+//! Remember the results of a function, for dynamic programming.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Counts of how often a [Memo] found a result it already knew.
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct MemoStats {
+    /// Number of lookups answered from the cache.
+    pub hits: usize,
+    /// Number of lookups that had to compute a new value.
+    pub misses: usize,
+}
+
+/// A cache of the values of some function for each key.
+///
+/// The simplest way to use it is [Memo::recurse], which takes a closure
+/// that is passed a function to call itself recursively, with every result
+/// remembered:
+///
+/// ```
+/// use aoclib::Memo;
+///
+/// let mut memo = Memo::new();
+/// let fib = memo.recurse(90, &|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+/// assert_eq!(fib, 2880067194370816120);
+/// assert_eq!(memo.len(), 91);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: MemoStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            stats: MemoStats::default(),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Memo::default()
+    }
+
+    /// Return the value of `f(key)`, where `f` is passed a function it can
+    /// use to recursively find the value for other keys.
+    ///
+    /// Each key's value is computed at most once, and remembered after this returns.
+    pub fn recurse<F>(&mut self, key: K, f: &F) -> V
+    where
+        K: Clone,
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(v) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return v.clone();
+        }
+        self.stats.misses += 1;
+        let v = f(&mut |k| self.recurse(k, f), key.clone());
+        self.cache.insert(key, v.clone());
+        v
+    }
+
+    /// Return the remembered value for `key`, or compute it with `f` and remember it.
+    ///
+    /// The key can be borrowed, such as a `&str` for a `String` key, and is
+    /// only copied if it's not already present.
+    pub fn get_or_insert_with<Q, F>(&mut self, key: &Q, f: F) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+        F: FnOnce() -> V,
+    {
+        if let Some(v) = self.cache.get(key) {
+            self.stats.hits += 1;
+            return v.clone();
+        }
+        self.stats.misses += 1;
+        let v = f();
+        self.cache.insert(key.to_owned(), v.clone());
+        v
+    }
+
+    /// Return the remembered value for `key`, if there is one.
+    ///
+    /// This doesn't count as a hit or miss.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get(key)
+    }
+
+    /// Remember a value for `key`, replacing any previous value.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// The number of values remembered.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Counts of cache hits and misses since the memo was made or cleared.
+    pub fn stats(&self) -> &MemoStats {
+        &self.stats
+    }
+
+    /// Forget all remembered values, and reset the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recurse_counts_hits() {
+        let mut memo = Memo::new();
+        // Paths through a grid, moving only right or down.
+        let paths = |m: &mut Memo<(u32, u32), u64>, x, y| {
+            m.recurse((x, y), &|paths, (x, y)| {
+                if x == 0 || y == 0 {
+                    1
+                } else {
+                    paths((x - 1, y)) + paths((x, y - 1))
+                }
+            })
+        };
+        assert_eq!(paths(&mut memo, 16, 16), 601080390);
+        // Every point except (0, 0), which is never reached, is computed once.
+        assert_eq!(memo.stats().misses, 17 * 17 - 1);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        let hits = memo.stats().hits;
+        assert!(hits > 0);
+        assert_eq!(paths(&mut memo, 3, 5), 56);
+        assert_eq!(memo.stats().hits, hits + 1);
+        assert_eq!(memo.get(&(3, 5)), Some(&56));
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), &MemoStats::default());
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut memo: Memo<String, usize> = Memo::new();
+        assert_eq!(memo.get_or_insert_with("hello", || 5), 5);
+        assert_eq!(memo.get_or_insert_with("hello", || unreachable!()), 5);
+        assert_eq!(memo.get("hello"), Some(&5));
+        assert_eq!(memo.stats(), &MemoStats { hits: 1, misses: 1 });
+    }
+}
@@ -0,0 +1,416 @@
+// Copyright 2026 Martin Pool
+
+//! Integers modulo some base, with arithmetic that wraps around, and affine
+//! maps `a * x + b` on them.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::numtheory::{mod_inverse, mod_pow, mul_mod};
+
+/// Operations shared by [ModInt] and [DynModInt].
+pub trait Modular:
+    Copy
+    + Eq
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    /// The value, in `0..modulus`.
+    fn value(&self) -> u64;
+
+    fn modulus(&self) -> u64;
+
+    /// Make a value with the same modulus as this one.
+    fn with_value(&self, value: i128) -> Self;
+
+    /// Raise this to the power `exp`.
+    #[must_use]
+    fn pow(self, exp: u128) -> Self {
+        self.with_value(mod_pow(self.value(), exp, self.modulus()) as i128)
+    }
+
+    /// The value that gives 1 when multiplied by this, or None if this has
+    /// a factor in common with the modulus.
+    fn inverse(self) -> Option<Self> {
+        mod_inverse(self.value(), self.modulus()).map(|v| self.with_value(v as i128))
+    }
+}
+
+/// An integer modulo `M`, where the modulus is known at compile time.
+///
+/// ```
+/// use aoclib::{ModInt, Modular};
+///
+/// type M7 = ModInt<7>;
+/// assert_eq!(M7::new(5) + M7::new(4), M7::new(2));
+/// assert_eq!(M7::new(3) * 5, M7::new(1));
+/// assert_eq!(M7::new(3).inverse(), Some(M7::new(5)));
+/// assert_eq!(M7::new(-1).value(), 6);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    /// Make a value, reducing it into `0..M`.
+    pub fn new(value: i128) -> Self {
+        const { assert!(M > 0, "modulus must be positive") };
+        ModInt {
+            value: value.rem_euclid(M as i128) as u64,
+        }
+    }
+}
+
+impl<const M: u64> Modular for ModInt<M> {
+    fn value(&self) -> u64 {
+        self.value
+    }
+
+    fn modulus(&self) -> u64 {
+        M
+    }
+
+    fn with_value(&self, value: i128) -> Self {
+        ModInt::new(value)
+    }
+}
+
+/// An integer modulo a base chosen at runtime, which is stored in every value.
+///
+/// Combining two values with different moduli panics.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    /// Make a value, reducing it into `0..modulus`.
+    pub fn new(value: i128, modulus: u64) -> Self {
+        assert!(modulus > 0, "modulus must be positive");
+        DynModInt {
+            value: value.rem_euclid(modulus as i128) as u64,
+            modulus,
+        }
+    }
+}
+
+impl Modular for DynModInt {
+    fn value(&self) -> u64 {
+        self.value
+    }
+
+    fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    fn with_value(&self, value: i128) -> Self {
+        DynModInt::new(value, self.modulus)
+    }
+}
+
+/// Implement arithmetic operators in terms of the value and modulus.
+///
+/// Plain `i64` operands are reduced by the same modulus.
+macro_rules! impl_ops {
+    ($t:ty $(, const $m:ident)?) => {
+        impl$(<const $m: u64>)? Add for $t {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                self.check(&rhs);
+                // Both are below the modulus, so subtracting it once is enough,
+                // even if the sum wraps around u64.
+                let (sum, overflow) = self.value.overflowing_add(rhs.value);
+                if overflow || sum >= self.modulus() {
+                    self.with_reduced(sum.wrapping_sub(self.modulus()))
+                } else {
+                    self.with_reduced(sum)
+                }
+            }
+        }
+
+        impl$(<const $m: u64>)? Sub for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                self.check(&rhs);
+                if self.value >= rhs.value {
+                    self.with_reduced(self.value - rhs.value)
+                } else {
+                    self.with_reduced(self.value + (self.modulus() - rhs.value))
+                }
+            }
+        }
+
+        impl$(<const $m: u64>)? Mul for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                self.check(&rhs);
+                self.with_reduced(mul_mod(self.value, rhs.value, self.modulus()))
+            }
+        }
+
+        /// Division multiplies by the inverse, and panics if there is none.
+        impl$(<const $m: u64>)? Div for $t {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, rhs: Self) -> Self {
+                self * rhs.inverse().expect("divisor has no inverse")
+            }
+        }
+
+        impl$(<const $m: u64>)? Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                self.with_reduced(0) - self
+            }
+        }
+
+        impl$(<const $m: u64>)? Add<i64> for $t {
+            type Output = Self;
+
+            fn add(self, rhs: i64) -> Self {
+                self + self.with_value(rhs as i128)
+            }
+        }
+
+        impl$(<const $m: u64>)? Sub<i64> for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: i64) -> Self {
+                self - self.with_value(rhs as i128)
+            }
+        }
+
+        impl$(<const $m: u64>)? Mul<i64> for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self {
+                self * self.with_value(rhs as i128)
+            }
+        }
+
+        impl$(<const $m: u64>)? AddAssign for $t {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl$(<const $m: u64>)? SubAssign for $t {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl$(<const $m: u64>)? MulAssign for $t {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl$(<const $m: u64>)? fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.value)
+            }
+        }
+
+        impl$(<const $m: u64>)? fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} (mod {})", self.value, self.modulus())
+            }
+        }
+    };
+}
+
+impl<const M: u64> ModInt<M> {
+    fn check(&self, _other: &Self) {}
+
+    /// Make a value that's already in `0..M`.
+    fn with_reduced(&self, value: u64) -> Self {
+        ModInt { value }
+    }
+}
+
+impl DynModInt {
+    fn check(&self, other: &Self) {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "can't combine values with different moduli"
+        );
+    }
+
+    /// Make a value that's already in `0..modulus`, with the same modulus.
+    fn with_reduced(&self, value: u64) -> Self {
+        DynModInt {
+            value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl_ops!(ModInt<M>, const M);
+impl_ops!(DynModInt);
+
+/// The map `x -> a * x + b` on modular integers.
+///
+/// Shuffling, rotating and reversing a deck of `m` cards are all maps like
+/// this on card positions, and so is any sequence of them. Maps can be
+/// composed, and a map repeated `n` times can be found in `O(log n)` steps.
+///
+/// ```
+/// use aoclib::{Affine, ModInt};
+///
+/// type M10 = ModInt<10>;
+/// // Reverse a deck of 10 cards, and then cut 3 from the top to the bottom.
+/// let reverse = Affine::new(M10::new(-1), M10::new(-1));
+/// let cut = Affine::new(M10::new(1), M10::new(-3));
+/// let both = reverse.compose(&cut);
+/// assert_eq!(both.apply(M10::new(0)), M10::new(6));
+/// assert_eq!(both.pow(2).apply(M10::new(0)), both.apply(both.apply(M10::new(0))));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Affine<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T: Modular> Affine<T> {
+    pub fn new(a: T, b: T) -> Self {
+        Affine { a, b }
+    }
+
+    /// The map that leaves every value unchanged, with the same modulus as this one.
+    #[must_use]
+    pub fn identity_like(&self) -> Self {
+        Affine {
+            a: self.a.with_value(1),
+            b: self.b.with_value(0),
+        }
+    }
+
+    /// Map `x`.
+    pub fn apply(&self, x: T) -> T {
+        self.a * x + self.b
+    }
+
+    /// Return the map that applies this map and then `then`.
+    #[must_use]
+    pub fn compose(&self, then: &Self) -> Self {
+        // then.a * (a * x + b) + then.b
+        Affine {
+            a: then.a * self.a,
+            b: then.a * self.b + then.b,
+        }
+    }
+
+    /// Return the map that applies this one `n` times.
+    #[must_use]
+    pub fn pow(&self, mut n: u128) -> Self {
+        let mut result = self.identity_like();
+        let mut square = *self;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.compose(&square);
+            }
+            square = square.compose(&square);
+            n >>= 1;
+        }
+        result
+    }
+
+    /// Return the map that undoes this one, or None if `a` has no inverse,
+    /// in which case several values are mapped to the same place.
+    pub fn inverse(&self) -> Option<Self> {
+        let inv = self.a.inverse()?;
+        Some(Affine {
+            a: inv,
+            b: -(inv * self.b),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn const_modulus_arithmetic() {
+        type M = ModInt<13>;
+        let a = M::new(9);
+        let b = M::new(-20);
+        assert_eq!(b.value(), 6);
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((a - b).value(), 3);
+        assert_eq!((a * b).value(), 2);
+        assert_eq!((-a).value(), 4);
+        assert_eq!((a / b) * b, a);
+        assert_eq!(a.pow(12), M::new(1));
+        assert_eq!(a + 5, M::new(1));
+        let mut c = a;
+        c += b;
+        c *= M::new(2);
+        c -= M::new(1);
+        assert_eq!(c.value(), 3);
+        assert_eq!(format!("{c} {c:?}"), "3 3 (mod 13)");
+        assert_eq!(ModInt::<12>::new(4).inverse(), None);
+    }
+
+    #[test]
+    fn large_modulus_does_not_overflow() {
+        let m = u64::MAX - 58; // The largest 64-bit prime.
+        let a = DynModInt::new(-2, m);
+        assert_eq!((a * a).value(), 4);
+        assert_eq!(a.pow(m as u128 - 1).value(), 1);
+        assert_eq!((a * a.inverse().unwrap()).value(), 1);
+        // The sum of two large values wraps around u64.
+        assert_eq!((a + a).value(), m - 4);
+        assert_eq!((a - (a + a)).value(), 2);
+        assert_eq!((-a).value(), 2);
+        assert_eq!((-(a - a)).value(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "different moduli")]
+    fn mismatched_moduli_panic() {
+        let _ = DynModInt::new(1, 5) + DynModInt::new(1, 7);
+    }
+
+    fn affine() -> impl Strategy<Value = Affine<DynModInt>> {
+        (1..50u64, any::<i64>(), any::<i64>()).prop_map(|(m, a, b)| {
+            Affine::new(DynModInt::new(a as i128, m), DynModInt::new(b as i128, m))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn pow_agrees_with_repeating(f in affine(), n in 0..40u128, x: i64) {
+            let x = f.a.with_value(x as i128);
+            let mut y = x;
+            for _ in 0..n {
+                y = f.apply(y);
+            }
+            prop_assert_eq!(f.pow(n).apply(x), y);
+        }
+
+        #[test]
+        fn compose_and_inverse(f in affine(), g in affine(), x: i64) {
+            let g = Affine::new(f.a.with_value(g.a.value() as i128), f.a.with_value(g.b.value() as i128));
+            let x = f.a.with_value(x as i128);
+            prop_assert_eq!(f.compose(&g).apply(x), g.apply(f.apply(x)));
+            if let Some(inv) = f.inverse() {
+                prop_assert_eq!(inv.apply(f.apply(x)), x);
+                prop_assert_eq!(f.compose(&inv), f.identity_like());
+            }
+        }
+    }
+}
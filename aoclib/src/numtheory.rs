@@ -0,0 +1,440 @@
+// Copyright 2026 Martin Pool
+
+//! Number theory on machine integers: gcd and lcm, modular arithmetic, and
+//! the Chinese Remainder Theorem.
+//!
+//! Everything works on signed and unsigned types up to 128 bits, including
+//! the minimum of a signed type, whose absolute value doesn't fit. Products
+//! are computed without overflow even when the modulus is close to the
+//! largest value of the type, and otherwise functions panic only if the
+//! result itself doesn't fit.
+//!
+//! Results of modular operations are always in `0..m`, even for negative
+//! inputs, and the modulus must be positive.
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// A primitive integer type that the functions in this module can use.
+pub trait Int:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The absolute value, or None for the minimum of a signed type, whose
+    /// absolute value doesn't fit.
+    fn checked_abs(self) -> Option<Self>;
+
+    /// The gcd of the absolute values, computed unsigned so that the minimum
+    /// of a signed type has an absolute value, or None if the result doesn't fit.
+    fn checked_gcd(self, other: Self) -> Option<Self>;
+
+    /// Reduce `self` into `0..m`.
+    fn modulo(self, m: Self) -> Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Return `a * b mod m`, without overflowing.
+    fn mul_mod(self, other: Self, m: Self) -> Self;
+}
+
+macro_rules! impl_int {
+    ($t:ty, $wide:ty, $unsigned_abs:expr, $from_unsigned:expr, $modulo:expr) => {
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_abs(self) -> Option<Self> {
+                $from_unsigned($unsigned_abs(self))
+            }
+
+            fn checked_gcd(self, other: Self) -> Option<Self> {
+                let (mut a, mut b) = ($unsigned_abs(self), $unsigned_abs(other));
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                $from_unsigned(a)
+            }
+
+            fn modulo(self, m: Self) -> Self {
+                $modulo(self, m)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn mul_mod(self, other: Self, m: Self) -> Self {
+                let a = self.modulo(m) as $wide;
+                let b = other.modulo(m) as $wide;
+                ((a * b) % m as $wide) as $t
+            }
+        }
+    };
+    ($t:ty, $unsigned_abs:expr, $from_unsigned:expr, $modulo:expr) => {
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_abs(self) -> Option<Self> {
+                $from_unsigned($unsigned_abs(self))
+            }
+
+            fn checked_gcd(self, other: Self) -> Option<Self> {
+                let (mut a, mut b) = ($unsigned_abs(self), $unsigned_abs(other));
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                $from_unsigned(a)
+            }
+
+            fn modulo(self, m: Self) -> Self {
+                $modulo(self, m)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn mul_mod(self, other: Self, m: Self) -> Self {
+                let a = self.modulo(m);
+                let b = other.modulo(m);
+                match a.checked_mul(b) {
+                    Some(p) => p % m,
+                    None => mul_mod_by_doubling(a, b, m),
+                }
+            }
+        }
+    };
+}
+
+impl_int!(u8, u128, |a| a, Some, |a, m| a % m);
+impl_int!(u16, u128, |a| a, Some, |a, m| a % m);
+impl_int!(u32, u128, |a| a, Some, |a, m| a % m);
+impl_int!(u64, u128, |a| a, Some, |a, m| a % m);
+impl_int!(usize, u128, |a| a, Some, |a, m| a % m);
+impl_int!(u128, |a| a, Some, |a, m| a % m);
+impl_int!(
+    i8,
+    i128,
+    i8::unsigned_abs,
+    |a| i8::try_from(a).ok(),
+    i8::rem_euclid
+);
+impl_int!(
+    i16,
+    i128,
+    i16::unsigned_abs,
+    |a| i16::try_from(a).ok(),
+    i16::rem_euclid
+);
+impl_int!(
+    i32,
+    i128,
+    i32::unsigned_abs,
+    |a| i32::try_from(a).ok(),
+    i32::rem_euclid
+);
+impl_int!(
+    i64,
+    i128,
+    i64::unsigned_abs,
+    |a| i64::try_from(a).ok(),
+    i64::rem_euclid
+);
+impl_int!(
+    isize,
+    i128,
+    isize::unsigned_abs,
+    |a| isize::try_from(a).ok(),
+    isize::rem_euclid
+);
+impl_int!(
+    i128,
+    i128::unsigned_abs,
+    |a| i128::try_from(a).ok(),
+    i128::rem_euclid
+);
+
+/// Return `a + b mod m` for `a` and `b` already in `0..m`, without overflowing.
+fn add_mod<T: Int>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Return `a - b mod m` for `a` and `b` already in `0..m`, without overflowing.
+fn sub_mod<T: Int>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+/// Multiply by adding doublings of `a`, for when the product is too big
+/// for the type.
+fn mul_mod_by_doubling<T: Int>(mut a: T, mut b: T, m: T) -> T {
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / two;
+    }
+    result
+}
+
+/// Return `a * b mod m`, without overflowing.
+pub fn mul_mod<T: Int>(a: T, b: T, m: T) -> T {
+    a.mul_mod(b, m)
+}
+
+/// The greatest common divisor of `a` and `b`, which is never negative.
+///
+/// `gcd(0, 0)` is 0. Panics if the result doesn't fit, which is only when
+/// the minimum of a signed type is paired with itself or with 0.
+pub fn gcd<T: Int>(a: T, b: T) -> T {
+    a.checked_gcd(b).expect("gcd overflows")
+}
+
+/// The least common multiple of `a` and `b`, which is never negative.
+///
+/// The lcm of 0 and anything is 0. Panics if the result overflows.
+pub fn lcm<T: Int>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    // The lcm is at least the gcd, so it overflows too if the gcd does.
+    a.checked_gcd(b)
+        .and_then(|g| (a / g).checked_mul(b))
+        .and_then(T::checked_abs)
+        .expect("lcm overflows")
+}
+
+/// The greatest common divisor of all the values, or 0 if there are none.
+pub fn gcd_all<T: Int>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The least common multiple of all the values, or 1 if there are none.
+///
+/// This is the length of the combined cycle of several things repeating at
+/// different periods.
+pub fn lcm_all<T: Int>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/// Return `(g, x, y)` such that `a * x + b * y == g`, where `g` is the gcd
+/// of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Return `x` in `0..m` such that `a * x mod m == 1`, or None if `a` and
+/// `m` have a common factor so there is no such `x`.
+pub fn mod_inverse<T: Int>(a: T, m: T) -> Option<T> {
+    assert!(m > T::ZERO, "modulus must be positive");
+    // The extended Euclidean algorithm, keeping only the coefficients of
+    // `a`, reduced mod `m` so that they never overflow or go negative.
+    let (mut old_r, mut r) = (a.modulo(m), m);
+    let (mut old_s, mut s) = (T::ONE.modulo(m), T::ZERO);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        let qs = q.mul_mod(s, m);
+        (old_s, s) = (s, sub_mod(old_s, qs, m));
+    }
+    (old_r == T::ONE).then_some(old_s)
+}
+
+/// Return `base` raised to `exp`, mod `m`.
+pub fn mod_pow<T: Int>(base: T, mut exp: u128, m: T) -> T {
+    assert!(m > T::ZERO, "modulus must be positive");
+    let mut base = base.modulo(m);
+    let mut result = T::ONE.modulo(m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.mul_mod(base, m);
+        }
+        base = base.mul_mod(base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solve a system of congruences `x ≡ r (mod m)` for each `(r, m)` pair, by
+/// the Chinese Remainder Theorem.
+///
+/// Returns `(x, l)` where `l` is the lcm of the moduli, and `x` in `0..l` is
+/// the smallest non-negative solution: every solution is `x` plus a multiple
+/// of `l`. With no congruences, that's `(0, 1)`.
+///
+/// The moduli don't need to be coprime. Returns None if the congruences
+/// contradict each other. Panics if the lcm of the moduli overflows.
+pub fn crt<T: Int>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (mut x, mut l) = (T::ZERO, T::ONE);
+    for (r, m) in congruences {
+        assert!(m > T::ZERO, "modulus must be positive");
+        // Find k such that x + l * k ≡ r (mod m), which means
+        // l * k ≡ r - x (mod m). This is only solvable if g divides r - x,
+        // and then k ≡ (r - x) / g * inverse(l / g) (mod m / g).
+        let g = gcd(l, m);
+        let diff = sub_mod(r.modulo(m), x.modulo(m), m);
+        if diff % g != T::ZERO {
+            return None;
+        }
+        let m_g = m / g;
+        let inv = mod_inverse(l / g, m_g).expect("l/g and m/g are coprime");
+        let k = (diff / g).mul_mod(inv, m_g);
+        let new_l = (l / g).checked_mul(m).expect("lcm of moduli overflows");
+        // l * k < l * m / g, which is the new lcm, so it can't overflow.
+        x = add_mod(x, l * k, new_l);
+        l = new_l;
+    }
+    Some((x, l))
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4i64, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(lcm_all([2usize, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+        assert_eq!(gcd_all(Vec::<u32>::new()), 0);
+    }
+
+    #[test]
+    fn signed_minimum() {
+        assert_eq!(gcd(i64::MIN, 4), 4);
+        assert_eq!(gcd(i128::MIN, -6), 2);
+        assert_eq!(gcd(i8::MIN, i8::MAX), 1);
+        assert_eq!(lcm(i64::MIN / 2, -4), 1 << 62);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm overflows")]
+    fn lcm_of_signed_minimum_overflows() {
+        lcm(i128::MIN, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd overflows")]
+    fn gcd_of_signed_minimum_and_zero_overflows() {
+        gcd(i32::MIN, 0);
+    }
+
+    #[test]
+    fn extended_gcd_coefficients() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        let (g, x, y) = extended_gcd(-35, 15);
+        assert_eq!(g, 5);
+        assert_eq!(-35 * x + 15 * y, 5);
+    }
+
+    #[test]
+    fn inverse_and_pow() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6u32, 9), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_pow(2u64, 10, 1000), 24);
+        assert_eq!(mod_pow(-2i64, 3, 7), 6);
+        assert_eq!(mod_pow(5u8, 0, 1), 0);
+        // Fermat's little theorem, with a prime too big to multiply naively.
+        let p: u128 = (1 << 127) - 1;
+        assert_eq!(mod_pow(3, p - 1, p), 1);
+        let a = 123456789123456789123456789u128;
+        assert_eq!(mul_mod(a, mod_inverse(a, p).unwrap(), p), 1);
+        let q: i128 = 170141183460469231731687303715884105727;
+        assert_eq!(mod_pow(-7i128, (q - 1) as u128, q), 1);
+    }
+
+    #[test]
+    fn crt_examples() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli with common factors.
+        assert_eq!(crt([(3u32, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt([(0u32, 4), (1, 6)]), None);
+        assert_eq!(crt(Vec::<(i64, i64)>::new()), Some((0, 1)));
+        // Negative residues are reduced.
+        assert_eq!(crt([(-1i64, 5), (-1, 7)]), Some((34, 35)));
+    }
+
+    proptest! {
+        #[test]
+        fn mul_mod_agrees_with_wide(a: u64, b: u64, m in 1..u64::MAX) {
+            let expected = (a as u128 * b as u128 % m as u128) as u64;
+            prop_assert_eq!(mul_mod(a, b, m), expected);
+            prop_assert_eq!(mul_mod(a as u128, b as u128, m as u128), expected as u128);
+        }
+
+        #[test]
+        fn mul_mod_128_by_doubling(a: u128, b: u128, m in 1..u128::MAX) {
+            // Check against splitting `b` into halves that can't overflow.
+            let r = mul_mod(a, b, m);
+            prop_assert!(r < m);
+            let b1 = b >> 64;
+            let b0 = b & u64::MAX as u128;
+            let shifted = mul_mod(mul_mod(a, b1, m), 1 << 64, m);
+            prop_assert_eq!(r, add_mod(shifted, mul_mod(a, b0, m), m));
+        }
+
+        #[test]
+        fn inverse_agrees_with_extended_gcd(a in -1000i64..1000, m in 1i64..1000) {
+            let (g, x, _) = extended_gcd(a as i128, m as i128);
+            match mod_inverse(a, m) {
+                Some(inv) => {
+                    prop_assert_eq!(g, 1);
+                    prop_assert_eq!(inv as i128, x.rem_euclid(m as i128));
+                    prop_assert_eq!(mul_mod(a, inv, m), 1 % m);
+                }
+                None => prop_assert!(g != 1),
+            }
+        }
+
+        #[test]
+        fn crt_agrees_with_search(congruences in prop::collection::vec((0u32..30, 1u32..12), 0..4)) {
+            let l = lcm_all(congruences.iter().map(|c| c.1));
+            let solution = (0..l).find(|x| congruences.iter().all(|&(r, m)| x % m == r % m));
+            prop_assert_eq!(crt(congruences.iter().copied()), solution.map(|x| (x, l)));
+        }
+    }
+}
@@ -12,43 +12,313 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::iter::FromIterator;
-use std::iter::IntoIterator;
+//! Permutations, combinations, and other ways to choose from a list.
+//!
+//! The iterators here borrow the items from a slice and yield a `Vec` of
+//! references for each choice, so the items need not be `Clone` and are
+//! generated lazily. To rearrange items in place without allocating
+//! anything, use [next_permutation] or [heap_permutations].
 
-/// Generate all permutations of the elements in l.
-pub fn permutations<T, I>(l: I) -> Vec<Vec<T>>
+/// Rearrange `v` into the next permutation in lexicographic order, and
+/// return true; or, if it's already the last one, sort it and return false.
+///
+/// Starting from a sorted slice, this visits every distinct permutation
+/// once, even if some items are equal.
+pub fn next_permutation<T: Ord>(v: &mut [T]) -> bool {
+    // Find the longest non-increasing suffix: it's already the last permutation
+    // of those items, so the item before it has to move up.
+    let Some(i) = (1..v.len()).rev().find(|&i| v[i - 1] < v[i]) else {
+        v.reverse();
+        return false;
+    };
+    let pivot = i - 1;
+    // Swap it with the smallest larger item in the suffix, which is the
+    // rightmost one since the suffix is non-increasing.
+    let j = (i..v.len()).rev().find(|&j| v[j] > v[pivot]).unwrap();
+    v.swap(pivot, j);
+    v[i..].reverse();
+    true
+}
+
+/// Call `f` with every permutation of `items`, using Heap's algorithm.
+///
+/// Each permutation differs from the previous one by a single swap, and
+/// there's no allocation beyond a counter per item. The items are left in
+/// some permutation of their original order.
+pub fn heap_permutations<T, F>(items: &mut [T], mut f: F)
 where
-    T: Clone,
-    I: IntoIterator<Item = T>,
+    F: FnMut(&[T]),
 {
-    let mut l = l.into_iter();
-    if let Some(first) = l.next() {
-        let rest = Vec::from_iter(l);
-        if rest.is_empty() {
-            vec![vec![first]]
+    let n = items.len();
+    let mut c = vec![0; n];
+    f(items);
+    let mut i = 1;
+    while i < n {
+        if c[i] < i {
+            if i % 2 == 0 {
+                items.swap(0, i);
+            } else {
+                items.swap(c[i], i);
+            }
+            f(items);
+            c[i] += 1;
+            i = 1;
         } else {
-            // The last element inserted at every possible position,
-            // in every possible permutation of the other members.
-            let mut r = Vec::new();
-            for p in permutations(rest) {
-                for i in 0..=(p.len()) {
-                    let mut q = p.clone();
-                    q.insert(i, first.clone());
-                    r.push(q);
+            c[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+fn pick<'a, T>(items: &'a [T], indices: &[usize]) -> Vec<&'a T> {
+    indices.iter().map(|&i| &items[i]).collect()
+}
+
+/// Iterate every ordering of `items`, in lexicographic order of their positions.
+///
+/// There are `n!` of them; for no items there's one empty ordering.
+pub fn permutations<T>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: Some((0..items.len()).collect()),
+    }
+}
+
+/// An iterator returned by [permutations].
+#[derive(Clone, Debug)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    /// The positions for the next permutation, or None when finished.
+    indices: Option<Vec<usize>>,
+}
+
+impl<'a, T> Iterator for Permutations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Vec<&'a T>> {
+        let indices = self.indices.as_mut()?;
+        let result = pick(self.items, indices);
+        if !next_permutation(indices) {
+            self.indices = None;
+        }
+        Some(result)
+    }
+}
+
+/// Iterate every way to choose `k` of `items`, ignoring order and without
+/// choosing any position twice.
+///
+/// The chosen items are in their original order, and the choices are
+/// in lexicographic order of positions.
+pub fn combinations<T>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: (k <= items.len()).then(|| (0..k).collect()),
+        repeat: false,
+    }
+}
+
+/// Iterate every way to choose `k` of `items`, ignoring order, where the
+/// same item may be chosen more than once.
+pub fn combinations_with_replacement<T>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: (k == 0 || !items.is_empty()).then(|| vec![0; k]),
+        repeat: true,
+    }
+}
+
+/// An iterator returned by [combinations] or [combinations_with_replacement].
+#[derive(Clone, Debug)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    /// The positions for the next combination, or None when finished.
+    indices: Option<Vec<usize>>,
+    /// True if positions can be repeated.
+    repeat: bool,
+}
+
+impl<'a, T> Iterator for Combinations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Vec<&'a T>> {
+        let indices = self.indices.as_mut()?;
+        let result = pick(self.items, indices);
+        let (n, k) = (self.items.len(), indices.len());
+        // Advance the rightmost position that can still move up, and reset
+        // those after it to their lowest allowed values.
+        let max_at = |i: usize| if self.repeat { n - 1 } else { n - k + i };
+        match (0..k).rev().find(|&i| indices[i] < max_at(i)) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in (i + 1)..k {
+                    indices[j] = if self.repeat {
+                        indices[i]
+                    } else {
+                        indices[j - 1] + 1
+                    };
                 }
             }
-            r
+            None => self.indices = None,
+        }
+        Some(result)
+    }
+}
+
+/// Iterate every subset of `items`, from the empty set to all of them.
+///
+/// Subsets are in the order of a binary counter where item `i` is bit `i`.
+///
+/// Panics if there are 64 or more items, which would be far too many subsets to visit.
+pub fn power_set<T>(items: &[T]) -> PowerSet<'_, T> {
+    assert!(items.len() < 64, "too many items for a power set");
+    PowerSet {
+        items,
+        next: 0,
+        end: 1 << items.len(),
+    }
+}
+
+/// An iterator returned by [power_set].
+#[derive(Clone, Debug)]
+pub struct PowerSet<'a, T> {
+    items: &'a [T],
+    next: u64,
+    end: u64,
+}
+
+impl<'a, T> Iterator for PowerSet<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Vec<&'a T>> {
+        if self.next == self.end {
+            return None;
+        }
+        let mask = self.next;
+        self.next += 1;
+        Some(
+            self.items
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, t)| t)
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.end - self.next) as usize;
+        (n, Some(n))
+    }
+}
+
+/// Iterate every way to choose one item from each of `lists`, varying the
+/// choice from the last list fastest.
+pub fn cartesian_product<T, L>(lists: &[L]) -> CartesianProduct<'_, T, L>
+where
+    L: AsRef<[T]>,
+{
+    CartesianProduct {
+        lists,
+        indices: lists
+            .iter()
+            .all(|l| !l.as_ref().is_empty())
+            .then(|| vec![0; lists.len()]),
+        _item: std::marker::PhantomData,
+    }
+}
+
+/// An iterator returned by [cartesian_product].
+#[derive(Clone, Debug)]
+pub struct CartesianProduct<'a, T, L> {
+    lists: &'a [L],
+    /// The position in each list for the next choice, or None when finished.
+    indices: Option<Vec<usize>>,
+    _item: std::marker::PhantomData<&'a T>,
+}
+
+impl<'a, T: 'a, L> Iterator for CartesianProduct<'a, T, L>
+where
+    L: AsRef<[T]>,
+{
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Vec<&'a T>> {
+        let indices = self.indices.as_mut()?;
+        let result = self
+            .lists
+            .iter()
+            .zip(indices.iter())
+            .map(|(l, &i)| &l.as_ref()[i])
+            .collect();
+        // Count up like an odometer.
+        let mut i = indices.len();
+        loop {
+            if i == 0 {
+                self.indices = None;
+                break;
+            }
+            i -= 1;
+            indices[i] += 1;
+            if indices[i] < self.lists[i].as_ref().len() {
+                break;
+            }
+            indices[i] = 0;
         }
-    } else {
-        vec![]
+        Some(result)
+    }
+}
+
+/// Iterate every way to write `n` as a sum of positive integers, ignoring order.
+///
+/// Each partition is in non-increasing order, starting with `[n]` and
+/// ending with `n` ones. There's one empty partition of 0.
+pub fn partitions(n: usize) -> Partitions {
+    Partitions {
+        next: Some(if n == 0 { Vec::new() } else { vec![n] }),
+    }
+}
+
+/// An iterator returned by [partitions].
+#[derive(Clone, Debug)]
+pub struct Partitions {
+    next: Option<Vec<usize>>,
+}
+
+impl Iterator for Partitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.next.take()?;
+        let mut parts = current.clone();
+        // Take one from the last part bigger than 1, and share it and all the
+        // ones after it into parts no bigger than that one.
+        if let Some(k) = parts.iter().rposition(|&p| p > 1) {
+            let mut rest = parts.len() - k;
+            parts.truncate(k + 1);
+            parts[k] -= 1;
+            let size = parts[k];
+            while rest > 0 {
+                parts.push(rest.min(size));
+                rest -= rest.min(size);
+            }
+            self.next = Some(parts);
+        }
+        Some(current)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
     fn pts(n: usize) -> String {
-        super::permutations(0..n)
-            .iter()
+        let items: Vec<usize> = (0..n).collect();
+        super::permutations(&items)
             .map(|e| {
                 e.iter()
                     .map(|i| i.to_string())
@@ -64,8 +334,112 @@ mod test {
         assert_eq!(pts(0), "");
         assert_eq!(pts(1), "0");
         assert_eq!(pts(2), "01,10");
-        assert_eq!(pts(3), "012,102,120,021,201,210");
+        assert_eq!(pts(3), "012,021,102,120,201,210");
 
-        assert_eq!(super::permutations(0..6).len(), 6 * 5 * 4 * 3 * 2);
+        assert_eq!(super::permutations(&[0; 6]).count(), 6 * 5 * 4 * 3 * 2);
+    }
+
+    #[test]
+    fn permutations_need_not_clone() {
+        struct NoClone(u32);
+        let items = [NoClone(1), NoClone(2), NoClone(3)];
+        let sums: Vec<u32> = permutations(&items)
+            .map(|p| p[0].0 * 100 + p[1].0 * 10 + p[2].0)
+            .collect();
+        assert_eq!(sums, [123, 132, 213, 231, 312, 321]);
+    }
+
+    #[test]
+    fn next_permutation_skips_duplicates() {
+        let mut v = [1, 1, 2];
+        let mut seen = vec![v];
+        while next_permutation(&mut v) {
+            seen.push(v);
+        }
+        assert_eq!(seen, [[1, 1, 2], [1, 2, 1], [2, 1, 1]]);
+        assert_eq!(v, [1, 1, 2]);
+    }
+
+    #[test]
+    fn heap_visits_every_permutation() {
+        let mut items = [0, 1, 2, 3];
+        let mut seen = HashSet::new();
+        heap_permutations(&mut items, |p| assert!(seen.insert(p.to_vec())));
+        assert_eq!(seen.len(), 24);
+    }
+
+    #[test]
+    fn combinations_in_order() {
+        let items = ['a', 'b', 'c', 'd'];
+        let s = |v: Vec<&char>| v.into_iter().collect::<String>();
+        let c: Vec<String> = combinations(&items, 2).map(s).collect();
+        assert_eq!(c, ["ab", "ac", "ad", "bc", "bd", "cd"]);
+        assert_eq!(combinations(&items, 0).count(), 1);
+        assert_eq!(combinations(&items, 5).count(), 0);
+        let r: Vec<String> = combinations_with_replacement(&items[..3], 2)
+            .map(s)
+            .collect();
+        assert_eq!(r, ["aa", "ab", "ac", "bb", "bc", "cc"]);
+        assert_eq!(combinations_with_replacement(&items[..0], 2).count(), 0);
+    }
+
+    #[test]
+    fn power_set_and_product() {
+        let subsets: Vec<Vec<&i32>> = power_set(&[1, 2, 3]).collect();
+        assert_eq!(subsets.len(), 8);
+        assert_eq!(subsets[0], Vec::<&i32>::new());
+        assert_eq!(subsets[5], [&1, &3]);
+        let lists = [vec!['x', 'y'], vec!['1', '2', '3']];
+        let p: Vec<String> = cartesian_product(&lists)
+            .map(|v| v.into_iter().collect())
+            .collect();
+        assert_eq!(p, ["x1", "x2", "x3", "y1", "y2", "y3"]);
+        let with_empty = [vec![1], vec![]];
+        assert_eq!(cartesian_product(&with_empty).count(), 0);
+    }
+
+    #[test]
+    fn partitions_of_five() {
+        let p: Vec<Vec<usize>> = partitions(5).collect();
+        assert_eq!(
+            p,
+            [
+                vec![5],
+                vec![4, 1],
+                vec![3, 2],
+                vec![3, 1, 1],
+                vec![2, 2, 1],
+                vec![2, 1, 1, 1],
+                vec![1, 1, 1, 1, 1]
+            ]
+        );
+        assert_eq!(partitions(0).collect::<Vec<_>>(), [Vec::<usize>::new()]);
+    }
+
+    proptest! {
+        #[test]
+        fn counts(n in 0..7usize, k in 0..5usize) {
+            let items: Vec<usize> = (0..n).collect();
+            let choose = |n: usize, k: usize| (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1));
+            prop_assert_eq!(combinations(&items, k).count(), if k <= n { choose(n, k) } else { 0 });
+            let with_repl = if n == 0 { usize::from(k == 0) } else { choose(n + k - 1, k) };
+            prop_assert_eq!(combinations_with_replacement(&items, k).count(), with_repl);
+            let all: HashSet<Vec<&usize>> = permutations(&items).collect();
+            prop_assert_eq!(all.len(), (1..=n).product::<usize>());
+            prop_assert_eq!(power_set(&items).count(), 1 << n);
+        }
+
+        #[test]
+        fn partitions_are_distinct_and_sum(n in 0..15usize) {
+            let mut seen = HashSet::new();
+            for p in partitions(n) {
+                prop_assert_eq!(p.iter().sum::<usize>(), n);
+                prop_assert!(p.windows(2).all(|w| w[0] >= w[1]));
+                prop_assert!(seen.insert(p));
+            }
+            // The number of partitions, from OEIS A000041.
+            let expected = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42, 56, 77, 101, 135];
+            prop_assert_eq!(seen.len(), expected[n]);
+        }
     }
 }
@@ -16,9 +16,12 @@
 //! Simple 2D integer-indexed point.
 use std::cmp::{max, min};
 use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 use strum_macros::EnumIter;
 
+use crate::numtheory::gcd;
+
 #[derive(Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct Point {
     pub x: isize,
@@ -55,6 +58,16 @@ impl Point {
         }
     }
 
+    /// Convert to unsigned coordinates.
+    ///
+    /// Panics if either coordinate is negative.
+    pub fn to_usizes(&self) -> (usize, usize) {
+        (
+            self.x.try_into().expect("x is not negative"),
+            self.y.try_into().expect("y is not negative"),
+        )
+    }
+
     pub const DIRECTIONS_8: &'static [(isize, isize)] = &[
         (1, 0),
         (-1, 0),
@@ -109,12 +122,150 @@ impl Point {
         (other.x - self.x).abs() + (other.y - self.y).abs()
     }
 
+    /// The number of king's moves between two points: the larger of the
+    /// x and y distances.
+    #[must_use]
+    pub fn chebyshev_distance(&self, other: &Point) -> isize {
+        max((other.x - self.x).abs(), (other.y - self.y).abs())
+    }
+
+    /// Return a point with each coordinate replaced by its sign: -1, 0, or 1.
+    ///
+    /// Applied to the difference between two points, this is the single
+    /// (possibly diagonal) step that moves one towards the other.
+    #[must_use]
+    pub fn signum(&self) -> Point {
+        point(self.x.signum(), self.y.signum())
+    }
+
+    /// Divide both coordinates by their greatest common divisor, giving the
+    /// smallest integer vector in the same direction.
+    ///
+    /// Two vectors from the origin lie on the same ray exactly when their
+    /// normalized forms are equal. The origin normalizes to itself.
+    #[must_use]
+    pub fn normalized(&self) -> Point {
+        let a = gcd(self.x, self.y);
+        if a == 0 {
+            *self
+        } else {
+            point(self.x / a, self.y / a)
+        }
+    }
+
+    /// Rotate 90 degrees clockwise around the origin, on a map where y runs down.
+    ///
+    /// This turns a [Dir] vector the same way as [Dir::turn_right].
+    #[must_use]
+    pub fn rotate_right(&self) -> Point {
+        point(-self.y, self.x)
+    }
+
+    /// Rotate 90 degrees anticlockwise around the origin, on a map where y runs down.
+    #[must_use]
+    pub fn rotate_left(&self) -> Point {
+        point(self.y, -self.x)
+    }
+
+    /// The dot product of two vectors.
+    #[must_use]
+    pub fn dot(&self, other: &Point) -> isize {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the cross product of two vectors in the plane.
+    ///
+    /// This is positive if `other` is clockwise from `self` on a map where
+    /// y runs down, negative if it's anticlockwise, and zero if they're
+    /// parallel.
+    #[must_use]
+    pub fn cross(&self, other: &Point) -> isize {
+        self.x * other.y - self.y * other.x
+    }
+
     /// Return the neighbor in a compass direction.
     #[must_use]
     pub fn step(&self, dir: Dir) -> Point {
         let (dx, dy) = dir.xy_delta();
         self.delta(dx, dy)
     }
+
+    /// Return the neighbor in one of the eight compass directions.
+    #[must_use]
+    pub fn step8(&self, dir: Dir8) -> Point {
+        let (dx, dy) = dir.xy_delta();
+        self.delta(dx, dy)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        point(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        point(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Point {
+        point(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        point(-self.x, -self.y)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Point {
+        point(x, y)
+    }
+}
+
+impl From<Point> for (isize, isize) {
+    fn from(p: Point) -> (isize, isize) {
+        (p.x, p.y)
+    }
+}
+
+/// The unit vector for a direction, on a map where y runs down.
+impl From<Dir> for Point {
+    fn from(dir: Dir) -> Point {
+        dir.xy_delta().into()
+    }
+}
+
+/// The unit vector for a direction, on a map where y runs down.
+impl From<Dir8> for Point {
+    fn from(dir: Dir8) -> Point {
+        dir.xy_delta().into()
+    }
 }
 
 impl std::str::FromStr for Point {
@@ -129,8 +280,12 @@ impl std::str::FromStr for Point {
     }
 }
 
-/// Return the points on a horizontal or vertical line between two points,
-/// including those two points.
+/// Return the points on a horizontal, vertical, or 45 degree diagonal line
+/// between two points, including those two points.
+///
+/// Points are returned in increasing order of x, or of y for vertical lines.
+///
+/// For lines at other angles, see [crate::line::Bresenham].
 pub fn line_between(p: Point, q: Point) -> Vec<Point> {
     if p.x == q.x {
         (min(p.y, q.y)..=max(p.y, q.y))
@@ -140,13 +295,19 @@ pub fn line_between(p: Point, q: Point) -> Vec<Point> {
         (min(p.x, q.x)..=max(p.x, q.x))
             .map(|x| point(x, p.y))
             .collect()
+    } else if (q.x - p.x).abs() == (q.y - p.y).abs() {
+        let (start, end) = if p.x < q.x { (p, q) } else { (q, p) };
+        let dy = (end.y - start.y).signum();
+        (0..=(end.x - start.x))
+            .map(|i| start.delta(i, i * dy))
+            .collect()
     } else {
-        panic!("points are not in a horizontal or vertical line");
+        panic!("points are not in a horizontal, vertical, or diagonal line");
     }
 }
 
 /// Compass directions
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, EnumIter)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, EnumIter)]
 pub enum Dir {
     N,
     S,
@@ -188,4 +349,368 @@ impl Dir {
     pub fn turn_left(&self) -> Dir {
         self.turn_right().invert()
     }
+
+    /// Turn relative to the current heading.
+    pub fn turn(&self, turn: Turn) -> Dir {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Straight => *self,
+            Turn::Right => self.turn_right(),
+            Turn::Back => self.invert(),
+        }
+    }
+
+    /// Parse a direction from an arrow (`^v<>`), a screen direction (`UDLR`),
+    /// or a compass point (`NSEW`).
+    pub fn from_char(c: char) -> Option<Dir> {
+        match c {
+            '^' | 'U' | 'N' => Some(Dir::N),
+            'v' | 'D' | 'S' => Some(Dir::S),
+            '>' | 'R' | 'E' => Some(Dir::E),
+            '<' | 'L' | 'W' => Some(Dir::W),
+            _ => None,
+        }
+    }
+
+    /// Parse a direction numbered clockwise from up: `0` is up, `1` right,
+    /// `2` down and `3` left.
+    pub fn from_urdl_digit(c: char) -> Option<Dir> {
+        match c {
+            '0' => Some(Dir::N),
+            '1' => Some(Dir::E),
+            '2' => Some(Dir::S),
+            '3' => Some(Dir::W),
+            _ => None,
+        }
+    }
+
+    /// Return the arrow character pointing this way.
+    pub fn to_arrow(&self) -> char {
+        match self {
+            Dir::N => '^',
+            Dir::S => 'v',
+            Dir::E => '>',
+            Dir::W => '<',
+        }
+    }
+}
+
+impl TryFrom<char> for Dir {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Dir, Self::Error> {
+        Dir::from_char(c).ok_or("unrecognized direction character")
+    }
+}
+
+/// A diagonal isn't a [Dir].
+impl TryFrom<Dir8> for Dir {
+    type Error = &'static str;
+
+    fn try_from(dir8: Dir8) -> Result<Dir, Self::Error> {
+        match dir8 {
+            Dir8::N => Ok(Dir::N),
+            Dir8::E => Ok(Dir::E),
+            Dir8::S => Ok(Dir::S),
+            Dir8::W => Ok(Dir::W),
+            _ => Err("diagonal direction has no Dir equivalent"),
+        }
+    }
+}
+
+/// Compass directions including the diagonals, listed clockwise from north.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, EnumIter)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    const CLOCKWISE: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    /// Rotate clockwise by some number of 45 degree steps.
+    fn rotate(&self, eighths: usize) -> Dir8 {
+        Dir8::CLOCKWISE[(*self as usize + eighths) % 8]
+    }
+
+    /// Return the relative x, y for this direction on a map where y runs down.
+    pub fn xy_delta(&self) -> (isize, isize) {
+        match self {
+            Dir8::N => (0, -1),
+            Dir8::NE => (1, -1),
+            Dir8::E => (1, 0),
+            Dir8::SE => (1, 1),
+            Dir8::S => (0, 1),
+            Dir8::SW => (-1, 1),
+            Dir8::W => (-1, 0),
+            Dir8::NW => (-1, -1),
+        }
+    }
+
+    /// True for the four diagonal directions.
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+
+    /// Return the opposite direction
+    pub fn invert(&self) -> Dir8 {
+        self.rotate(4)
+    }
+
+    /// Turn 45 degrees clockwise.
+    pub fn turn_right_45(&self) -> Dir8 {
+        self.rotate(1)
+    }
+
+    /// Turn 45 degrees anticlockwise.
+    pub fn turn_left_45(&self) -> Dir8 {
+        self.rotate(7)
+    }
+
+    pub fn turn_right(&self) -> Dir8 {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(&self) -> Dir8 {
+        self.rotate(6)
+    }
+
+    /// Turn relative to the current heading, in 90 degree steps.
+    pub fn turn(&self, turn: Turn) -> Dir8 {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Straight => *self,
+            Turn::Right => self.turn_right(),
+            Turn::Back => self.invert(),
+        }
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(dir: Dir) -> Dir8 {
+        match dir {
+            Dir::N => Dir8::N,
+            Dir::E => Dir8::E,
+            Dir::S => Dir8::S,
+            Dir::W => Dir8::W,
+        }
+    }
+}
+
+/// A turn relative to the current heading.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, EnumIter)]
+pub enum Turn {
+    Left,
+    Straight,
+    Right,
+    Back,
+}
+
+impl Turn {
+    /// Return the turn that changes heading from `from` to `to`.
+    pub fn between(from: Dir, to: Dir) -> Turn {
+        if to == from {
+            Turn::Straight
+        } else if to == from.turn_left() {
+            Turn::Left
+        } else if to == from.turn_right() {
+            Turn::Right
+        } else {
+            Turn::Back
+        }
+    }
+
+    /// Return the turn that undoes this one.
+    pub fn invert(&self) -> Turn {
+        match self {
+            Turn::Left => Turn::Right,
+            Turn::Right => Turn::Left,
+            t => *t,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let p = point(3, -4);
+        let q = point(-1, 2);
+        assert_eq!(p + q, point(2, -2));
+        assert_eq!(p - q, point(4, -6));
+        assert_eq!(p * 3, point(9, -12));
+        assert_eq!(-p, point(-3, 4));
+        let mut r = p;
+        r += q;
+        assert_eq!(r, point(2, -2));
+        r -= q;
+        assert_eq!(r, p);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Point::from((1, 2)), point(1, 2));
+        assert_eq!(<(isize, isize)>::from(point(1, 2)), (1, 2));
+        for dir in Dir::iter() {
+            assert_eq!(point(5, 5) + dir.into(), point(5, 5).step(dir));
+        }
+    }
+
+    #[test]
+    fn usizes() {
+        assert_eq!(Point::from_usizes(3, 4).to_usizes(), (3, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_to_usizes() {
+        point(-1, 0).to_usizes();
+    }
+
+    #[test]
+    fn distances() {
+        let p = point(1, 1);
+        let q = point(4, -1);
+        assert_eq!(p.manhattan_distance(&q), 5);
+        assert_eq!(p.chebyshev_distance(&q), 3);
+        assert_eq!(q.chebyshev_distance(&p), 3);
+    }
+
+    #[test]
+    fn signum_and_normalized() {
+        assert_eq!(point(7, -3).signum(), point(1, -1));
+        assert_eq!(point(0, 5).signum(), point(0, 1));
+        assert_eq!(point(6, -4).normalized(), point(3, -2));
+        assert_eq!(point(0, -5).normalized(), point(0, -1));
+        assert_eq!(point(3, 5).normalized(), point(3, 5));
+        assert_eq!(point(0, 0).normalized(), point(0, 0));
+    }
+
+    #[test]
+    fn rotation_matches_turns() {
+        for dir in Dir::iter() {
+            let v = Point::from(dir);
+            assert_eq!(v.rotate_right(), dir.turn_right().into());
+            assert_eq!(v.rotate_left(), dir.turn_left().into());
+        }
+        let p = point(2, 7);
+        assert_eq!(p.rotate_right().rotate_left(), p);
+        assert_eq!(p.rotate_right().rotate_right(), -p);
+    }
+
+    #[test]
+    fn dir8_turns() {
+        assert_eq!(Dir8::N.turn_right_45(), Dir8::NE);
+        assert_eq!(Dir8::N.turn_left_45(), Dir8::NW);
+        assert_eq!(Dir8::NW.turn_right(), Dir8::NE);
+        assert_eq!(Dir8::SE.invert(), Dir8::NW);
+        for d in Dir8::iter() {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(Point::from(d.invert()), -Point::from(d));
+            assert_eq!(Point::from(d.turn_right()), Point::from(d).rotate_right());
+            let p = Point::from(d);
+            assert_eq!(d.is_diagonal(), p.x != 0 && p.y != 0);
+        }
+        let from_dir8: Vec<Point> = Dir8::iter().map(Point::from).collect();
+        for &(dx, dy) in Point::DIRECTIONS_8 {
+            assert!(from_dir8.contains(&point(dx, dy)));
+        }
+    }
+
+    #[test]
+    fn dir_and_dir8_conversion() {
+        for d in Dir::iter() {
+            let d8 = Dir8::from(d);
+            assert_eq!(Dir::try_from(d8), Ok(d));
+            assert_eq!(d8.xy_delta(), d.xy_delta());
+            for t in Turn::iter() {
+                assert_eq!(Dir8::from(d.turn(t)), d8.turn(t));
+            }
+        }
+        assert!(Dir::try_from(Dir8::SW).is_err());
+    }
+
+    #[test]
+    fn relative_turns() {
+        for from in Dir::iter() {
+            for t in Turn::iter() {
+                let to = from.turn(t);
+                assert_eq!(Turn::between(from, to), t);
+                assert_eq!(to.turn(t.invert()), from);
+            }
+        }
+        // Carts in 2018 day 13 turn left, straight, right, and repeat at each intersection.
+        let cycle = [Turn::Left, Turn::Straight, Turn::Right];
+        let mut dir = Dir::E;
+        for i in 0..4 {
+            dir = dir.turn(cycle[i % 3]);
+        }
+        assert_eq!(dir, Dir::N);
+    }
+
+    #[test]
+    fn parse_dir() {
+        for (s, dir) in [
+            ("^UN0", Dir::N),
+            ("vDS2", Dir::S),
+            (">RE1", Dir::E),
+            ("<LW3", Dir::W),
+        ] {
+            let mut cs = s.chars();
+            for c in cs.by_ref().take(3) {
+                assert_eq!(Dir::from_char(c), Some(dir));
+                assert_eq!(Dir::try_from(c), Ok(dir));
+            }
+            assert_eq!(Dir::from_urdl_digit(cs.next().unwrap()), Some(dir));
+            assert_eq!(Dir::from_char(dir.to_arrow()), Some(dir));
+        }
+        assert_eq!(Dir::from_char('x'), None);
+        assert!(Dir::try_from('4').is_err());
+        assert_eq!(Dir::from_urdl_digit('4'), None);
+    }
+
+    #[test]
+    fn line_between_diagonal() {
+        assert_eq!(
+            line_between(point(3, 1), point(1, 3)),
+            [point(1, 3), point(2, 2), point(3, 1)]
+        );
+        assert_eq!(line_between(point(4, 0), point(2, 0)).len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn line_between_other_angle() {
+        line_between(point(0, 0), point(2, 1));
+    }
+
+    #[test]
+    fn dot_and_cross() {
+        let n: Point = Dir::N.into();
+        let e: Point = Dir::E.into();
+        assert_eq!(n.dot(&e), 0);
+        assert_eq!(n.dot(&n), 1);
+        assert!(n.cross(&e) > 0, "east is clockwise from north");
+        assert!(e.cross(&n) < 0);
+        assert_eq!(point(2, 4).cross(&point(1, 2)), 0);
+    }
 }
@@ -0,0 +1,153 @@
+// Copyright 2026 Martin Pool
+
+//! A radix heap: a min-heap for integer priorities that never go below
+//! the last one popped, as in Djikstra's method with non-negative step costs.
+//!
+//! Entries are kept in 65 buckets according to the highest bit where their
+//! priority differs from the last priority popped. Bucket 0 holds entries
+//! equal to the last popped priority, and bucket `b` holds entries that
+//! first differ at bit `b - 1`. When bucket 0 is empty, the smallest entry
+//! in the next non-empty bucket becomes the new last priority and that
+//! bucket's entries are redistributed to lower buckets. Each entry can
+//! only move down, so this takes amortized `O(log C)` time per entry,
+//! where `C` is the range of priorities, and there are no comparisons
+//! between items.
+
+/// A min-heap of items with `u64` priorities, where every priority pushed
+/// must be at least the last priority popped.
+#[derive(Debug)]
+pub struct RadixHeap<T> {
+    last: u64,
+    len: usize,
+    buckets: Vec<Vec<(u64, T)>>,
+}
+
+impl<T> Default for RadixHeap<T> {
+    fn default() -> Self {
+        RadixHeap::new()
+    }
+}
+
+impl<T> RadixHeap<T> {
+    pub fn new() -> Self {
+        RadixHeap {
+            last: 0,
+            len: 0,
+            buckets: (0..=64).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    /// Return the number of entries in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The priority of the last item popped, which is a lower bound on
+    /// everything still in the heap.
+    pub fn last_priority(&self) -> u64 {
+        self.last
+    }
+
+    fn bucket(&self, priority: u64) -> usize {
+        (64 - (priority ^ self.last).leading_zeros()) as usize
+    }
+
+    /// Insert an item.
+    ///
+    /// Panics if `priority` is less than the last priority popped.
+    pub fn push(&mut self, priority: u64, item: T) {
+        assert!(
+            priority >= self.last,
+            "priority {priority} is below last popped priority {}",
+            self.last
+        );
+        let b = self.bucket(priority);
+        self.buckets[b].push((priority, item));
+        self.len += 1;
+    }
+
+    /// Remove and return an item with the lowest priority, and its priority.
+    ///
+    /// Items with equal priority are returned in no particular order.
+    pub fn pop(&mut self) -> Option<(u64, T)> {
+        if self.buckets[0].is_empty() {
+            let b = self.buckets.iter().position(|b| !b.is_empty())?;
+            let entries = std::mem::take(&mut self.buckets[b]);
+            self.last = entries.iter().map(|(p, _)| *p).min().unwrap();
+            for (p, item) in entries {
+                let nb = self.bucket(p);
+                debug_assert!(nb < b);
+                self.buckets[nb].push((p, item));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    /// Discard all entries, and allow any priority to be pushed again.
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(Vec::clear);
+        self.len = 0;
+        self.last = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn pops_in_order() {
+        let mut heap = RadixHeap::new();
+        for p in [5, 3, 9, 3, 1 << 40, 0] {
+            heap.push(p, p * 10);
+        }
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.pop(), Some((0, 0)));
+        assert_eq!(heap.pop(), Some((3, 30)));
+        heap.push(4, 40);
+        let rest: Vec<u64> = std::iter::from_fn(|| heap.pop().map(|(p, _)| p)).collect();
+        assert_eq!(rest, [3, 4, 5, 9, 1 << 40]);
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_decreasing_priority() {
+        let mut heap = RadixHeap::new();
+        heap.push(10, ());
+        heap.pop();
+        heap.push(9, ());
+    }
+
+    proptest! {
+        #[test]
+        fn monotone_agrees_with_sort(ops: Vec<Option<u32>>) {
+            // Pushes are relative to the last priority popped, so they're always allowed.
+            let mut heap = RadixHeap::new();
+            let mut model: Vec<u64> = Vec::new();
+            for op in ops {
+                match op {
+                    Some(delta) => {
+                        let p = heap.last_priority() + delta as u64;
+                        heap.push(p, ());
+                        model.push(p);
+                    }
+                    None => {
+                        model.sort_unstable_by(|a, b| b.cmp(a));
+                        prop_assert_eq!(heap.pop().map(|(p, _)| p), model.pop());
+                    }
+                }
+                prop_assert_eq!(heap.len(), model.len());
+            }
+        }
+    }
+}
@@ -1,6 +1,12 @@
 use std::cmp::{max, min};
 use std::ops::Range;
 
+mod interval_set;
+mod range_map;
+
+pub use interval_set::IntervalSet;
+pub use range_map::RangeMap;
+
 pub trait RangeExt: Sized {
     fn overlap(&self, other: &Self) -> [Self; 3];
 }
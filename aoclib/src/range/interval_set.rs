@@ -0,0 +1,276 @@
+// Copyright 2026 Martin Pool
+
+//! A set of values stored as sorted, disjoint, half-open ranges.
+
+use std::iter::Sum;
+use std::ops::{Range, Sub};
+
+use super::RangeExt;
+
+/// A set of values of `T`, such as integers, stored as ranges.
+///
+/// This is efficient when the set is made of a few long runs, like the
+/// cells covered by a row of sensors, however many values they contain.
+///
+/// The ranges are kept sorted, non-empty, and separated by gaps: ranges
+/// that overlap or touch are merged as they're inserted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    /// Construct an empty set.
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// True if the set contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges that make up the set, in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Iterate the disjoint ranges that make up the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    /// The number of disjoint ranges in the set.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The smallest range that covers the whole set, or None if it's empty.
+    pub fn span(&self) -> Option<Range<T>> {
+        Some(self.ranges.first()?.start..self.ranges.last()?.end)
+    }
+
+    /// True if `t` is in the set.
+    pub fn contains(&self, t: &T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= *t);
+        self.ranges.get(i).is_some_and(|r| r.start <= *t)
+    }
+
+    /// True if every value of `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Add all the values in `range` to the set.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Ranges that overlap or touch the new range are merged with it.
+        let i = self.ranges.partition_point(|r| r.end < range.start);
+        let j = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if i < j {
+            range.start.min(self.ranges[i].start)..range.end.max(self.ranges[j - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    /// Remove all the values in `range` from the set.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        let j = self.ranges.partition_point(|r| r.start < range.end);
+        let remains: Vec<Range<T>> = self.ranges[i..j]
+            .iter()
+            .flat_map(|r| {
+                let [before, _, after] = r.overlap(&range);
+                [before, after]
+            })
+            .filter(|r| !r.is_empty())
+            .collect();
+        self.ranges.splice(i..j, remains);
+    }
+
+    /// Return the values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter().cloned());
+        result
+    }
+
+    /// Return the values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(ra), Some(rb)) = (a.peek(), b.peek()) {
+            let [_, both, _] = ra.overlap(rb);
+            if !both.is_empty() {
+                ranges.push(both);
+            }
+            // Whichever ends first can't overlap anything else in the other set.
+            if ra.end <= rb.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Return the values in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for r in other.iter() {
+            result.remove(r.clone());
+        }
+        result
+    }
+
+    /// Return the values within `bounds` that are not in this set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        IntervalSet::from(bounds).difference(self)
+    }
+
+    /// Iterate the gaps between successive ranges of the set, in ascending order.
+    ///
+    /// This does not include anything before the first range or after the last.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.windows(2).map(|w| w[0].end..w[1].start)
+    }
+
+    /// The total number of values in the set.
+    pub fn total_len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    /// Check that the ranges are non-empty, sorted, and separated by gaps.
+    ///
+    /// Panics on failure.
+    pub fn assert_valid(&self)
+    where
+        T: std::fmt::Debug,
+    {
+        for r in &self.ranges {
+            assert!(!r.is_empty(), "empty range {r:?}");
+        }
+        for w in self.ranges.windows(2) {
+            assert!(w[0].end < w[1].start, "{:?} is not before {:?}", w[0], w[1]);
+        }
+    }
+}
+
+impl<T: Ord + Copy> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<It: IntoIterator<Item = Range<T>>>(iter: It) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord + Copy> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<It: IntoIterator<Item = Range<T>>>(&mut self, iter: It) {
+        for r in iter {
+            self.insert(r)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn insert_merges_touching_ranges() {
+        let mut set: IntervalSet<i32> = [10..14, 16..20, 3..6, 12..18].into_iter().collect();
+        assert_eq!(set.ranges(), [3..6, 10..20]);
+        assert_eq!(set.total_len(), 13);
+        set.insert(6..8);
+        assert_eq!(set.ranges(), [3..8, 10..20]);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![8..10]);
+        assert!(set.contains(&3));
+        assert!(!set.contains(&8));
+        assert!(set.contains_range(&(11..20)));
+        assert!(!set.contains_range(&(7..11)));
+        assert_eq!(set.span(), Some(3..20));
+    }
+
+    #[test]
+    fn remove_splits_ranges() {
+        let mut set = IntervalSet::from(0..20);
+        set.remove(5..6);
+        set.remove(18..30);
+        assert_eq!(set.ranges(), [0..5, 6..18]);
+        assert_eq!(set.complement(-5..25).ranges(), [-5..0, 5..6, 18..25]);
+    }
+
+    /// A set of small integers, expanded into the values it contains.
+    fn values(set: &IntervalSet<i8>) -> BTreeSet<i8> {
+        set.iter().flat_map(|r| r.clone()).collect()
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<i8>>> {
+        prop::collection::vec((-20..20i8, 0..10i8).prop_map(|(a, l)| a..(a + l)), 0..8)
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_btreeset(a in ranges(), b in ranges()) {
+            let sa: IntervalSet<i8> = a.iter().cloned().collect();
+            let sb: IntervalSet<i8> = b.iter().cloned().collect();
+            sa.assert_valid();
+            let va = values(&sa);
+            let vb = values(&sb);
+            prop_assert_eq!(&va, &a.iter().flat_map(|r| r.clone()).collect());
+            prop_assert_eq!(sa.total_len() as usize, va.len());
+            for i in -30..30 {
+                prop_assert_eq!(sa.contains(&i), va.contains(&i));
+            }
+            let union = sa.union(&sb);
+            union.assert_valid();
+            prop_assert_eq!(values(&union), &va | &vb);
+            let intersection = sa.intersection(&sb);
+            intersection.assert_valid();
+            prop_assert_eq!(values(&intersection), &va & &vb);
+            let difference = sa.difference(&sb);
+            difference.assert_valid();
+            prop_assert_eq!(values(&difference), &va - &vb);
+            let complement = sa.complement(-10..10);
+            complement.assert_valid();
+            prop_assert_eq!(values(&complement), (-10..10).filter(|i| !va.contains(i)).collect());
+        }
+    }
+}
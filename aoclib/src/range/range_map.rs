@@ -0,0 +1,284 @@
+// Copyright 2026 Martin Pool
+
+//! A piecewise map from values to values, where each piece shifts a range
+//! of sources onto a range of destinations of the same length.
+
+use std::ops::{Add, Range, Sub};
+
+use super::{IntervalSet, RangeExt};
+
+/// Maps ranges of source values onto destination ranges, and leaves every
+/// value outside those ranges unchanged.
+///
+/// For example, a piece from `10..15` to `20` maps 10 to 20 and 14 to 24.
+///
+/// Maps can be applied to whole [IntervalSet]s at once, splitting ranges
+/// where they cross the edges of pieces, and a chain of maps can be
+/// composed into one map so that values only need to be looked up once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    /// Source ranges and the destination of their start, sorted and disjoint.
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        RangeMap { pieces: Vec::new() }
+    }
+}
+
+impl<T> RangeMap<T>
+where
+    T: Ord + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// Construct a map that leaves every value unchanged.
+    pub fn new() -> Self {
+        RangeMap::default()
+    }
+
+    /// True if this map leaves every value unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.pieces.iter().all(|(src, dest)| src.start == *dest)
+    }
+
+    /// The pieces of the map: each source range and where its start is mapped to.
+    pub fn pieces(&self) -> &[(Range<T>, T)] {
+        &self.pieces
+    }
+
+    /// Map the values in `src` to consecutive values starting at `dest`.
+    ///
+    /// Panics if `src` overlaps any range already in the map.
+    pub fn insert(&mut self, src: Range<T>, dest: T) {
+        if src.is_empty() {
+            return;
+        }
+        let i = self.pieces.partition_point(|(s, _)| s.end <= src.start);
+        assert!(
+            self.pieces.get(i).is_none_or(|(s, _)| src.end <= s.start),
+            "source range overlaps an existing piece"
+        );
+        self.pieces.insert(i, (src, dest));
+    }
+
+    /// Return where `t` is mapped to.
+    pub fn lookup(&self, t: T) -> T {
+        let i = self.pieces.partition_point(|(s, _)| s.end <= t);
+        match self.pieces.get(i) {
+            Some((src, dest)) if src.start <= t => *dest + (t - src.start),
+            _ => t,
+        }
+    }
+
+    /// Split `range` where it crosses the edges of pieces, returning each
+    /// part of it in ascending order along with the range it's mapped to.
+    pub fn map_range(&self, range: Range<T>) -> Vec<(Range<T>, Range<T>)> {
+        let mut parts = Vec::new();
+        let mut rest = range;
+        let first = self.pieces.partition_point(|(s, _)| s.end <= rest.start);
+        for (src, dest) in &self.pieces[first..] {
+            if rest.is_empty() || src.start >= rest.end {
+                break;
+            }
+            let [before, inside, after] = rest.overlap(src);
+            if !before.is_empty() {
+                parts.push((before.clone(), before));
+            }
+            let start = *dest + (inside.start - src.start);
+            parts.push((inside.clone(), start..(start + (inside.end - inside.start))));
+            rest = after;
+        }
+        if !rest.is_empty() {
+            parts.push((rest.clone(), rest));
+        }
+        parts
+    }
+
+    /// Return the set of values that the values in `set` are mapped to.
+    pub fn apply(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter()
+            .flat_map(|r| self.map_range(r.clone()))
+            .map(|(_, dest)| dest)
+            .collect()
+    }
+
+    /// Return a single map equivalent to applying this map and then `then`.
+    pub fn compose(&self, then: &RangeMap<T>) -> RangeMap<T> {
+        let mut pieces = Vec::new();
+        // Values moved by this map go wherever `then` sends their destination.
+        for (src, dest) in &self.pieces {
+            let image = *dest..(*dest + (src.end - src.start));
+            for (part, mapped) in then.map_range(image) {
+                let start = src.start + (part.start - *dest);
+                pieces.push((start..(start + (part.end - part.start)), mapped.start));
+            }
+        }
+        // Values this map leaves alone are only moved by `then`.
+        let moved: IntervalSet<T> = self.pieces.iter().map(|(s, _)| s.clone()).collect();
+        for (src, dest) in &then.pieces {
+            for part in IntervalSet::from(src.clone()).difference(&moved).iter() {
+                pieces.push((part.clone(), *dest + (part.start - src.start)));
+            }
+        }
+        pieces.sort_by_key(|(s, _)| s.start);
+        RangeMap::normalized(pieces)
+    }
+
+    /// Return the map that undoes this one.
+    ///
+    /// Fails if two values are mapped to the same place, in which case
+    /// there's no inverse.
+    pub fn invert(&self) -> Result<RangeMap<T>, &'static str> {
+        // Every value is mapped to a different place exactly when the pieces'
+        // destinations are disjoint and exactly cover their sources:
+        // otherwise something would land on top of a value left unchanged.
+        let sources: IntervalSet<T> = self.pieces.iter().map(|(s, _)| s.clone()).collect();
+        let mut images: Vec<(Range<T>, T)> = self
+            .pieces
+            .iter()
+            .map(|(src, dest)| (*dest..(*dest + (src.end - src.start)), src.start))
+            .collect();
+        images.sort_by_key(|(d, _)| d.start);
+        if images.windows(2).any(|w| w[0].0.end > w[1].0.start) {
+            return Err("two pieces map to overlapping destinations");
+        }
+        let covered: IntervalSet<T> = images.iter().map(|(d, _)| d.clone()).collect();
+        if covered != sources {
+            return Err("pieces map onto values that are left unchanged");
+        }
+        Ok(RangeMap::normalized(images))
+    }
+
+    /// Build a map from sorted disjoint pieces, dropping pieces that change
+    /// nothing and joining pieces that continue one another.
+    fn normalized(pieces: Vec<(Range<T>, T)>) -> RangeMap<T> {
+        let mut result: Vec<(Range<T>, T)> = Vec::with_capacity(pieces.len());
+        for (src, dest) in pieces {
+            if src.is_empty() || src.start == dest {
+                continue;
+            }
+            if let Some((last_src, last_dest)) = result.last_mut() {
+                if last_src.end == src.start && *last_dest + (src.start - last_src.start) == dest {
+                    last_src.end = src.end;
+                    continue;
+                }
+            }
+            result.push((src, dest));
+        }
+        RangeMap { pieces: result }
+    }
+}
+
+impl<T> FromIterator<(Range<T>, T)> for RangeMap<T>
+where
+    T: Ord + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<It: IntoIterator<Item = (Range<T>, T)>>(iter: It) -> Self {
+        let mut map = RangeMap::new();
+        for (src, dest) in iter {
+            map.insert(src, dest);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn map_range_splits_at_pieces() {
+        let map: RangeMap<u64> = [(10..15, 20)].into_iter().collect();
+        assert_eq!(map.lookup(9), 9);
+        assert_eq!(map.lookup(10), 20);
+        assert_eq!(map.lookup(14), 24);
+        assert_eq!(map.lookup(15), 15);
+        assert_eq!(map.map_range(0..10), [(0..10, 0..10)]);
+        assert_eq!(
+            map.map_range(0..20),
+            [(0..10, 0..10), (10..15, 20..25), (15..20, 15..20)]
+        );
+        let image = map.apply(&IntervalSet::from(0..20));
+        assert_eq!(image.ranges(), [0..10, 15..25]);
+    }
+
+    #[test]
+    fn compose_and_invert() {
+        // Swap 0..5 with 5..10, and then shift 3..8 up to 13..18.
+        let swap: RangeMap<u64> = [(0..5, 5), (5..10, 0)].into_iter().collect();
+        let shift: RangeMap<u64> = [(3..8, 13)].into_iter().collect();
+        let both = swap.compose(&shift);
+        for x in 0..20 {
+            assert_eq!(both.lookup(x), shift.lookup(swap.lookup(x)), "{x}");
+        }
+        assert!(swap.compose(&swap).is_identity());
+        assert_eq!(swap.invert(), Ok(swap.clone()));
+        assert!(shift.invert().is_err());
+    }
+
+    /// A map built from pieces that are skipped if they would overlap.
+    fn map() -> impl Strategy<Value = RangeMap<u32>> {
+        prop::collection::vec((0..40u32, 1..10u32, 0..40u32), 0..6).prop_map(|pieces| {
+            let mut map = RangeMap::new();
+            let mut used = IntervalSet::new();
+            for (start, len, dest) in pieces {
+                let src = start..(start + len);
+                if used
+                    .intersection(&IntervalSet::from(src.clone()))
+                    .is_empty()
+                {
+                    used.insert(src.clone());
+                    map.insert(src, dest);
+                }
+            }
+            map
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn compose_agrees_with_lookup(f in map(), g in map()) {
+            let h = f.compose(&g);
+            for x in 0..100 {
+                prop_assert_eq!(h.lookup(x), g.lookup(f.lookup(x)));
+            }
+        }
+
+        #[test]
+        fn apply_agrees_with_lookup(f in map(), start in 0..50u32, len in 0..30u32) {
+            let image = f.apply(&IntervalSet::from(start..(start + len)));
+            let mut expected: Vec<u32> = (start..(start + len)).map(|x| f.lookup(x)).collect();
+            expected.sort_unstable();
+            expected.dedup();
+            let values: Vec<u32> = image.iter().flat_map(|r| r.clone()).collect();
+            prop_assert_eq!(values, expected);
+        }
+
+        #[test]
+        fn invert_undoes_permutation(lens in prop::collection::vec(1..8u32, 1..6), rotate in 0..6usize) {
+            // Cut 0..n into blocks and rearrange them, which is always invertible.
+            let mut blocks = Vec::new();
+            let mut start = 0;
+            for len in lens {
+                blocks.push(start..(start + len));
+                start += len;
+            }
+            let mut dests = blocks.clone();
+            let rotate = rotate % dests.len();
+            dests.rotate_left(rotate);
+            let mut next = 0;
+            let mut f = RangeMap::new();
+            for d in dests {
+                f.insert(d.clone(), next);
+                next += d.end - d.start;
+            }
+            let inverse = f.invert().unwrap();
+            prop_assert!(f.compose(&inverse).is_identity());
+            for x in 0..(start + 5) {
+                prop_assert_eq!(inverse.lookup(f.lookup(x)), x);
+            }
+        }
+    }
+}
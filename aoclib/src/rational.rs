@@ -0,0 +1,316 @@
+// Copyright 2026 Martin Pool
+
+//! Exact fractions, for solving equations without rounding.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::numtheory::gcd;
+use crate::BigInt;
+
+/// A fraction of two `i128`s, always kept in lowest terms with a positive
+/// denominator, so that equal values compare equal.
+///
+/// The `checked_` methods return None if the result doesn't fit; the
+/// operators panic instead, rather than silently wrapping.
+///
+/// ```
+/// use aoclib::Rational;
+///
+/// let x = Rational::new(1, 3) + Rational::new(1, 6);
+/// assert_eq!(x, Rational::new(1, 2));
+/// assert_eq!(x * 4, Rational::from(2));
+/// assert_eq!((x * 4).to_integer(), Some(2));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Make the fraction `num / den`.
+    ///
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert_ne!(den, 0, "denominator is zero");
+        Rational::checked_new(num, den).expect("rational overflows")
+    }
+
+    /// Make the fraction `num / den`, or None if `den` is zero.
+    pub fn checked_new(num: i128, den: i128) -> Option<Rational> {
+        if den == 0 {
+            return None;
+        }
+        if num == 0 {
+            return Some(Rational::ZERO);
+        }
+        // Reduce by the gcd, computed unsigned so that i128::MIN has an
+        // absolute value.
+        let g = gcd(num.unsigned_abs(), den.unsigned_abs());
+        let (num, den) = match i128::try_from(g) {
+            Ok(g) => (num / g, den / g),
+            // The gcd of two non-zero values only exceeds i128::MAX when
+            // both are i128::MIN.
+            Err(_) => return Some(Rational::ONE),
+        };
+        if den < 0 {
+            Some(Rational {
+                num: num.checked_neg()?,
+                den: den.checked_neg()?,
+            })
+        } else {
+            Some(Rational { num, den })
+        }
+    }
+
+    /// The numerator, which has the sign of the value.
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    /// The denominator, which is always positive.
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Return the value if it's a whole number.
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    /// The largest integer not greater than this value.
+    pub fn floor(&self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    /// Return 1 divided by this, or None if it's zero.
+    pub fn recip(&self) -> Option<Rational> {
+        if self.num == 0 {
+            None
+        } else {
+            Rational::checked_new(self.den, self.num)
+        }
+    }
+
+    pub fn checked_add(&self, other: &Rational) -> Option<Rational> {
+        // Multiply through by the lcm of the denominators, rather than their
+        // product, to stay small.
+        let g = gcd(self.den, other.den);
+        let num = self
+            .num
+            .checked_mul(other.den / g)?
+            .checked_add(other.num.checked_mul(self.den / g)?)?;
+        Rational::checked_new(num, (self.den / g).checked_mul(other.den)?)
+    }
+
+    pub fn checked_sub(&self, other: &Rational) -> Option<Rational> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    pub fn checked_mul(&self, other: &Rational) -> Option<Rational> {
+        // Cancel common factors across the two fractions first.
+        // The denominators are positive, so these are too.
+        let g1 = gcd(self.num.unsigned_abs(), other.den as u128) as i128;
+        let g2 = gcd(other.num.unsigned_abs(), self.den as u128) as i128;
+        Rational::checked_new(
+            (self.num / g1).checked_mul(other.num / g2)?,
+            (self.den / g2).checked_mul(other.den / g1)?,
+        )
+    }
+
+    /// Divide, returning None if `other` is zero or the result overflows.
+    pub fn checked_div(&self, other: &Rational) -> Option<Rational> {
+        self.checked_mul(&other.recip()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Rational> {
+        Some(Rational {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Rational {
+        Rational { num: n, den: 1 }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Rational {
+            fn from(n: $t) -> Rational {
+                Rational::from(n as i128)
+            }
+        })*
+    };
+}
+
+from_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive so cross-multiplying keeps the order;
+        // if that overflows, do it in a BigInt.
+        match (
+            self.num.checked_mul(other.den),
+            other.num.checked_mul(self.den),
+        ) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => (BigInt::from(self.num) * BigInt::from(other.den))
+                .cmp(&(BigInt::from(other.num) * BigInt::from(self.den))),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        self.checked_neg().expect("rational overflows")
+    }
+}
+
+/// Implement an operator that panics when the checked form fails, for
+/// rationals and for integers on the right.
+macro_rules! impl_op {
+    ($tr:ident $f:ident $checked:ident $msg:literal) => {
+        impl $tr for Rational {
+            type Output = Rational;
+
+            fn $f(self, rhs: Rational) -> Rational {
+                self.$checked(&rhs).expect($msg)
+            }
+        }
+
+        impl $tr<i128> for Rational {
+            type Output = Rational;
+
+            fn $f(self, rhs: i128) -> Rational {
+                self.$checked(&Rational::from(rhs)).expect($msg)
+            }
+        }
+    };
+}
+
+impl_op!(Add add checked_add "rational overflows");
+impl_op!(Sub sub checked_sub "rational overflows");
+impl_op!(Mul mul checked_mul "rational overflows");
+impl_op!(Div div checked_div "division by zero or overflow");
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl fmt::Debug for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl FromStr for Rational {
+    type Err = &'static str;
+
+    /// Parse an integer, or a fraction like `-3/4`.
+    fn from_str(s: &str) -> Result<Rational, &'static str> {
+        let (num, den) = s.split_once('/').unwrap_or((s, "1"));
+        let num = num.trim().parse().map_err(|_| "invalid numerator")?;
+        let den = den.trim().parse().map_err(|_| "invalid denominator")?;
+        Rational::checked_new(num, den).ok_or("denominator is zero")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn normalized() {
+        let r = Rational::new(6, -8);
+        assert_eq!((r.numer(), r.denom()), (-3, 4));
+        assert_eq!(r.to_string(), "-3/4");
+        assert_eq!(r.floor(), -1);
+        assert_eq!("-6/8".parse(), Ok(r));
+        assert_eq!("5".parse(), Ok(Rational::from(5)));
+        assert!("1/0".parse::<Rational>().is_err());
+        assert_eq!(Rational::new(0, -5), Rational::ZERO);
+        assert_eq!(Rational::new(i128::MIN, i128::MIN), Rational::ONE);
+        assert_eq!(Rational::checked_new(0, i128::MIN), Some(Rational::ZERO));
+        assert_eq!(Rational::checked_new(i128::MIN, 0), None);
+        assert_eq!(Rational::checked_new(i128::MIN, -1), None);
+        assert_eq!(Rational::new(i128::MIN, 2).numer(), i128::MIN / 2);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Rational::new(2, 3);
+        let b = Rational::new(-5, 4);
+        assert_eq!(a + b, Rational::new(-7, 12));
+        assert_eq!(a - b, Rational::new(23, 12));
+        assert_eq!(a * b, Rational::new(-5, 6));
+        assert_eq!(a / b, Rational::new(-8, 15));
+        assert_eq!(-a, Rational::new(-2, 3));
+        assert_eq!(a * 3, Rational::from(2));
+        assert_eq!(Rational::ZERO.recip(), None);
+        assert_eq!(a.checked_div(&Rational::ZERO), None);
+    }
+
+    #[test]
+    fn overflow_is_detected() {
+        let big = Rational::from(i128::MAX);
+        assert_eq!(big.checked_add(&Rational::ONE), None);
+        assert_eq!(big.checked_mul(&Rational::from(2)), None);
+        // Cancelling before multiplying avoids overflow here.
+        let x = Rational::new(i128::MAX, 3);
+        assert_eq!(x * Rational::new(3, i128::MAX), Rational::ONE);
+        // Comparison falls back to big integers.
+        assert!(
+            Rational::new(i128::MAX, i128::MAX - 1) < Rational::new(i128::MAX - 1, i128::MAX - 2)
+        );
+        assert!(Rational::new(i128::MAX - 1, i128::MAX) < Rational::ONE);
+    }
+
+    fn small() -> impl Strategy<Value = Rational> {
+        (-1000..1000i128, 1..1000i128).prop_map(|(n, d)| Rational::new(n, d))
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_cross_multiplying(a in small(), b in small()) {
+            let (p, q, r, s) = (a.numer(), a.denom(), b.numer(), b.denom());
+            prop_assert_eq!(a + b, Rational::new(p * s + r * q, q * s));
+            prop_assert_eq!(a - b, Rational::new(p * s - r * q, q * s));
+            prop_assert_eq!(a * b, Rational::new(p * r, q * s));
+            if r != 0 {
+                prop_assert_eq!(a / b, Rational::new(p * s, q * r));
+            }
+            prop_assert_eq!(a.cmp(&b), (p * s).cmp(&(r * q)));
+            prop_assert_eq!(a.to_string().parse::<Rational>(), Ok(a));
+        }
+    }
+}
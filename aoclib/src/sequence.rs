@@ -0,0 +1,194 @@
+// Copyright 2026 Martin Pool
+
+//! Predict more terms of a sequence that's generated by a polynomial, such
+//! as a count that grows quadratically with the number of steps.
+//!
+//! The sequence is detected from a sample of values at consecutive
+//! positions, by taking differences between neighboring values until they
+//! become constant. This only succeeds if the sample is long enough to show
+//! that the differences really are constant: a polynomial of degree `d`
+//! needs at least `d + 2` values.
+
+use crate::Rational;
+
+type Result<T> = std::result::Result<T, &'static str>;
+
+/// Return the first value of each row of differences, down to the first
+/// row that's constant.
+fn leading_differences(values: &[i128]) -> Result<Vec<i128>> {
+    let mut row = values.to_vec();
+    let mut leading = Vec::new();
+    loop {
+        if row.len() < 2 {
+            return Err("sequence is not polynomial within the sample");
+        }
+        leading.push(row[0]);
+        if row.iter().all(|x| *x == row[0]) {
+            return Ok(leading);
+        }
+        row = row
+            .windows(2)
+            .map(|w| w[1].checked_sub(w[0]).ok_or("difference overflows"))
+            .collect::<Result<_>>()?;
+    }
+}
+
+/// Return the degree of the polynomial that generates `values`, taken at
+/// consecutive positions.
+///
+/// A constant sequence has degree 0, and an arithmetic sequence has degree 1.
+pub fn degree(values: &[i128]) -> Result<usize> {
+    Ok(leading_differences(values)?.len() - 1)
+}
+
+/// Return the value at `index` of the polynomial sequence whose first values are
+/// `values`, so that `values[i]` is at index `i`.
+///
+/// The index can be past the end of the sample, or negative to run the
+/// sequence backwards.
+pub fn extrapolate(values: &[i128], index: i128) -> Result<i128> {
+    // Newton's forward difference formula: the value at n is the sum of
+    // C(n, j) times the leading value of the j'th differences.
+    let mut sum: i128 = 0;
+    let mut binomial: i128 = 1;
+    for (j, d) in leading_differences(values)?.into_iter().enumerate() {
+        let j = j as i128;
+        if j > 0 {
+            // C(n, j) = C(n, j-1) * (n - j + 1) / j, which always divides exactly.
+            binomial = index
+                .checked_sub(j - 1)
+                .and_then(|f| binomial.checked_mul(f))
+                .ok_or("value overflows")?
+                / j;
+        }
+        sum = binomial
+            .checked_mul(d)
+            .and_then(|t| sum.checked_add(t))
+            .ok_or("value overflows")?;
+    }
+    Ok(sum)
+}
+
+/// Return the value after the last in a polynomial sequence.
+pub fn next_value(values: &[i128]) -> Result<i128> {
+    extrapolate(values, values.len() as i128)
+}
+
+/// Return the value before the first in a polynomial sequence.
+pub fn previous_value(values: &[i128]) -> Result<i128> {
+    extrapolate(values, -1)
+}
+
+/// Evaluate at `x` the lowest-degree polynomial that passes through all the
+/// `(x, y)` points, by Lagrange interpolation.
+///
+/// Unlike [extrapolate] the points needn't be evenly spaced, but nothing
+/// checks that they come from a polynomial of lower degree: any `n` points
+/// fit a polynomial of degree `n - 1`.
+///
+/// Fails if two points have the same `x`, if the result is not an integer,
+/// or if any intermediate value overflows.
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Result<i128> {
+    let mut sum = Rational::ZERO;
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term = Rational::from(yi);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                if xi == xj {
+                    return Err("two points have the same x");
+                }
+                term = x
+                    .checked_sub(xj)
+                    .zip(xi.checked_sub(xj))
+                    .and_then(|(num, den)| Rational::checked_new(num, den))
+                    .and_then(|f| term.checked_mul(&f))
+                    .ok_or("value overflows")?;
+            }
+        }
+        sum = sum.checked_add(&term).ok_or("value overflows")?;
+    }
+    sum.to_integer().ok_or("value is not an integer")
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn extrapolate_examples() {
+        assert_eq!(next_value(&[0, 3, 6, 9, 12, 15]), Ok(18));
+        assert_eq!(next_value(&[10, 13, 16, 21, 30, 45]), Ok(68));
+        assert_eq!(previous_value(&[10, 13, 16, 21, 30, 45]), Ok(5));
+        assert_eq!(degree(&[1, 3, 6, 10, 15, 21]), Ok(2));
+        assert_eq!(degree(&[7, 7]), Ok(0));
+        assert_eq!(
+            extrapolate(&[1, 4, 9, 16], 1_000_000),
+            Ok(1_000_002_000_001)
+        );
+        assert_eq!(extrapolate(&[1, 4, 9, 16], -5), Ok(16));
+    }
+
+    #[test]
+    fn large_index() {
+        // The answer fits even though the next binomial coefficient wouldn't.
+        assert_eq!(extrapolate(&[0, 1, 2], 1 << 70), Ok(1 << 70));
+        assert_eq!(extrapolate(&[0, 1, 2], i128::MIN), Ok(i128::MIN));
+        assert_eq!(extrapolate(&[0, 1, 4, 9], 1 << 70), Err("value overflows"));
+    }
+
+    #[test]
+    fn not_polynomial() {
+        assert!(degree(&[]).is_err());
+        assert!(degree(&[4]).is_err());
+        // Powers of two are never polynomial, and would need more values to be sure.
+        assert!(next_value(&[1, 2, 4, 8, 16, 32]).is_err());
+        assert!(degree(&[1, 2, 4]).is_err());
+    }
+
+    #[test]
+    fn lagrange_examples() {
+        // The quadratic through these points, evaluated far away.
+        let points = [(65, 3734), (196, 33285), (327, 92268)];
+        assert_eq!(lagrange(&points, 26501365), Ok(602259568764234));
+        assert_eq!(
+            lagrange(&[(0, 0), (2, 1)], 1),
+            Err("value is not an integer")
+        );
+        assert_eq!(lagrange(&[(0, 0), (2, 4), (4, 16)], 3), Ok(9));
+        assert!(lagrange(&[(1, 2), (1, 3)], 0).is_err());
+        assert_eq!(lagrange(&[], 5), Ok(0));
+        assert_eq!(
+            lagrange(&[(1, 0), (2, 1)], i128::MIN),
+            Err("value overflows")
+        );
+        assert_eq!(
+            lagrange(&[(i128::MIN, 0), (i128::MAX, 1)], 0),
+            Err("value overflows")
+        );
+    }
+
+    fn eval(coeffs: &[i128], x: i128) -> i128 {
+        coeffs.iter().rev().fold(0, |acc, c| acc * x + c)
+    }
+
+    proptest! {
+        #[test]
+        fn recovers_polynomials(
+            coeffs in prop::collection::vec(-20..20i128, 0..5),
+            leading in prop_oneof![-20..0i128, 1..20i128],
+            extra in 0..4usize,
+            index in -50..50i128,
+        ) {
+            let mut coeffs = coeffs;
+            coeffs.push(leading);
+            let d = coeffs.len() - 1;
+            let values: Vec<i128> = (0..(d + 2 + extra) as i128).map(|x| eval(&coeffs, x)).collect();
+            prop_assert_eq!(degree(&values), Ok(d));
+            prop_assert_eq!(extrapolate(&values, index), Ok(eval(&coeffs, index)));
+            let points: Vec<(i128, i128)> = (0..=d as i128).map(|i| (3 * i - 7, eval(&coeffs, 3 * i - 7))).collect();
+            prop_assert_eq!(lagrange(&points, index), Ok(eval(&coeffs, index)));
+        }
+    }
+}
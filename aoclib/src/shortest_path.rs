@@ -13,13 +13,33 @@
 // limitations under the License.
 
 //! Find the shortest path in a graph, using Djikstra's method.
+//!
+//! Searches are silent by default. To watch a long search, pass an [Observer]
+//! such as [PrintProgress] to [ShortestPath::find_with_observer], or one of
+//! the other `_with_observer` functions.
+//!
+//! When every step costs 1, [ShortestPath::find_bfs] is faster, and when every
+//! step costs 0 or 1, so is [ShortestPath::find_01].
+//!
+//! For very large spaces, [ShortestPath::find_bidirectional] explores fewer
+//! states when the graph can be walked backwards, and
+//! [ShortestPath::find_ida_star] needs memory only for the current path.
 
-use std::collections::HashMap;
+use std::cmp::max;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::ops::Add;
+use std::time::{Duration, Instant};
 
-use crate::MinHeap;
+use crate::KeyedHeap;
+
+pub(crate) mod bfs;
+mod bidirectional;
+mod distance_map;
+mod ida_star;
+
+pub use distance_map::{pairwise_distances, DistanceMap};
 
 /// A discovered shortest path in a space.
 ///
@@ -29,7 +49,7 @@ use crate::MinHeap;
 /// `usize`, `f64`, etc.
 pub struct ShortestPath<P, D>
 where
-    P: Eq + Ord + Clone + Hash + Debug,
+    P: Eq + Clone + Hash + Debug,
     D: Ord + Add<Output = D> + Clone + Default + Debug,
 {
     distance: D,
@@ -38,14 +58,83 @@ where
 }
 
 /// Statistics about the work done to find the path.
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
 pub struct Stats {
+    /// Number of entries taken from the frontier, including outdated entries
+    /// for states that were later reached by a shorter path.
     pub search_cycles: usize,
+    /// Number of distinct states whose neighbors were examined.
+    pub states_examined: usize,
+    /// Number of times a state already in the frontier was pushed again
+    /// because a shorter path to it was found.
+    pub duplicate_pushes: usize,
+    /// The largest number of entries in the frontier at any time.
+    pub max_frontier: usize,
+    /// Wall-clock time taken by the search.
+    pub elapsed: Duration,
+}
+
+/// Receives events from a search in progress, for example to debug or
+/// report progress on a long search.
+///
+/// Every method does nothing by default, and `()` is an observer that
+/// ignores everything.
+pub trait Observer<P, D> {
+    /// State `p`, at distance `d` from the origin, was taken from the
+    /// frontier and its neighbors are about to be examined.
+    fn on_pop(&mut self, _p: &P, _d: &D) {}
+
+    /// A new shortest known path to `to` was found through `from`, with
+    /// total distance `d`.
+    fn on_relax(&mut self, _from: &P, _to: &P, _d: &D) {}
+
+    /// The search reached destination `p` at distance `d`.
+    fn on_goal(&mut self, _p: &P, _d: &D, _stats: &Stats) {}
+
+    /// The search ran out of states without reaching a destination.
+    fn on_exhausted(&mut self, _stats: &Stats) {}
+}
+
+impl<P, D> Observer<P, D> for () {}
+
+/// An [Observer] that prints to stderr every `every` states examined, and
+/// when the search finishes.
+#[derive(Debug)]
+pub struct PrintProgress {
+    every: usize,
+    pops: usize,
+}
+
+impl PrintProgress {
+    pub fn new(every: usize) -> PrintProgress {
+        assert!(every > 0);
+        PrintProgress { every, pops: 0 }
+    }
+}
+
+impl<P: Debug, D: Debug> Observer<P, D> for PrintProgress {
+    fn on_pop(&mut self, p: &P, d: &D) {
+        self.pops += 1;
+        if self.pops.is_multiple_of(self.every) {
+            eprintln!(
+                "shortest_path: {} states, distance={d:?} p={p:?}",
+                self.pops
+            );
+        }
+    }
+
+    fn on_goal(&mut self, _p: &P, d: &D, stats: &Stats) {
+        eprintln!("shortest_path: destination found at distance {d:?}, {stats:?}");
+    }
+
+    fn on_exhausted(&mut self, stats: &Stats) {
+        eprintln!("shortest_path: no path found, {stats:?}");
+    }
 }
 
 impl<P, D> ShortestPath<P, D>
 where
-    P: Eq + Ord + Clone + Hash + Debug,
+    P: Eq + Clone + Hash + Debug,
     D: Ord + Add<Output = D> + Clone + Default + Debug,
 {
     /// Find the shortest path using
@@ -62,62 +151,33 @@ where
         neighbors: NbrFn,
     ) -> Option<Self>
     where
-        NbrFn: Fn(&P) -> Nbrs,
+        NbrFn: FnMut(&P) -> Nbrs,
         Nbrs: IntoIterator<Item = (P, D)>,
         EstFn: Fn(&P) -> D,
     {
-        // Next points to visit, indexed by distance so far.
-        let mut queue = MinHeap::<(D, P)>::new();
-        // Shortest known distance to reach any point.
-        let mut best = HashMap::<P, D>::new();
-        // The previous state that leads, on the best path, to this state.
-        let mut predecessor = HashMap::<P, P>::new();
-        let mut search_cycles = 0;
-        queue.push((Default::default(), origin.clone()));
-        best.insert(origin.clone(), Default::default());
-        loop {
-            let (_priority, p) = queue
-                .pop()
-                .expect("heap is empty without reaching destination");
-            search_cycles += 1;
-            if search_cycles % 100000 == 0 {
-                dbg!(&p);
-                queue.assert_valid();
-            }
-            let est = estimate(&p);
-            if est == D::default() {
-                // Reassemble (a) shortest path to the destination by looking backwards
-                // at the step that led to each point.
-                let distance = best[&p].clone();
-                let mut path = vec![p];
-                while let Some(next) = predecessor.get(path.last().unwrap()) {
-                    path.push(next.clone());
-                }
-                path.reverse();
-                return Some(ShortestPath {
-                    distance,
-                    path,
-                    stats: Stats { search_cycles },
-                });
-            }
-            debug_assert!(
-                est > D::default(),
-                "estimate {:?} of {:?} is negative",
-                est,
-                &p
-            );
-            for (np, step) in neighbors(&p) {
-                let nd = step + best[&p].clone();
-                if let Some(prev_d) = best.get(&np) {
-                    if nd >= *prev_d {
-                        continue; // Already found a shorter path; don't revisit.
-                    }
-                }
-                best.insert(np.clone(), nd.clone());
-                queue.push((nd + est.clone(), np.clone()));
-                predecessor.insert(np, p.clone());
-            }
-        }
+        Self::find_astar_with_observer(origin, estimate, neighbors, &mut ())
+    }
+
+    /// Find the shortest path using A* search, reporting progress to an [Observer].
+    pub fn find_astar_with_observer<NbrFn, Nbrs, EstFn, Obs>(
+        origin: &P,
+        estimate: EstFn,
+        neighbors: NbrFn,
+        observer: &mut Obs,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+        EstFn: Fn(&P) -> D,
+        Obs: Observer<P, D>,
+    {
+        search(
+            [(origin.clone(), D::default())],
+            |p| estimate(p) == D::default(),
+            &estimate,
+            neighbors,
+            observer,
+        )
     }
 
     /// Find the shortest path in a graph, using Djikstra's method.
@@ -127,67 +187,113 @@ where
     /// `is_destination` returns true for the destination point.
     ///
     /// `neighbors` returns a `Vec` of neighbors for a given point, and the
-    /// incremental distance to them.
+    /// incremental distance to them. It may be `FnMut` so that it can cache
+    /// expensive calculations about the space.
     pub fn find<NbrFn, Nbrs, DestFn>(
         origin: &P,
         is_destination: DestFn,
         neighbors: NbrFn,
     ) -> Option<Self>
     where
-        NbrFn: Fn(&P) -> Nbrs,
+        NbrFn: FnMut(&P) -> Nbrs,
         Nbrs: IntoIterator<Item = (P, D)>,
         DestFn: Fn(&P) -> bool,
     {
-        // Next points to visit, indexed by distance so far.
-        let mut queue = MinHeap::<(D, P)>::new();
-        // Shortest known distance to reach any point.
-        let mut best = HashMap::<P, D>::new();
-        // The previous state that leads, on the best path, to this state.
-        let mut predecessor = HashMap::<P, P>::new();
-        queue.push((Default::default(), origin.clone()));
-        best.insert(origin.clone(), Default::default());
-        let mut search_cycles = 0;
-        loop {
-            search_cycles += 1;
-            if let Some((d, p)) = queue.pop() {
-                if search_cycles % 100000 == 0 {
-                    println!("best distance={d:?} p={p:?}");
-                    // dbg!(&d, &p);
-                    queue.assert_valid();
-                }
-                if is_destination(&p) {
-                    // Reassemble (a) shortest path to the destination by looking backwards
-                    // at the step that led to each point.
-                    let mut path = vec![p];
-                    while let Some(next) = predecessor.get(path.last().unwrap()) {
-                        path.push(next.clone());
-                    }
-                    path.reverse();
-                    println!(
-                        "shortest_path: destination found at distance {d:?} {search_cycles} search cycles, {} states examined",
-                        best.len()
-                    );
-                    return Some(ShortestPath {
-                        distance: d,
-                        path,
-                        stats: Stats { search_cycles },
-                    });
-                }
-                for (np, step) in neighbors(&p) {
-                    let nd = step + d.clone();
-                    if let Some(prev_d) = best.get(&np) {
-                        if nd >= *prev_d {
-                            continue; // Already found a shorter path; don't revisit.
-                        }
-                    }
-                    best.insert(np.clone(), nd.clone());
-                    queue.push((nd, np.clone()));
-                    predecessor.insert(np, p.clone());
-                }
-            } else {
-                return None;
-            }
-        }
+        Self::find_with_observer(origin, is_destination, neighbors, &mut ())
+    }
+
+    /// Find the shortest path using Djikstra's method, reporting progress to an [Observer].
+    pub fn find_with_observer<NbrFn, Nbrs, DestFn, Obs>(
+        origin: &P,
+        is_destination: DestFn,
+        neighbors: NbrFn,
+        observer: &mut Obs,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+        DestFn: Fn(&P) -> bool,
+        Obs: Observer<P, D>,
+    {
+        search(
+            [(origin.clone(), D::default())],
+            is_destination,
+            |_| D::default(),
+            neighbors,
+            observer,
+        )
+    }
+
+    /// Find the shortest path from `origin` to `destination` by searching
+    /// forwards from the origin and backwards from the destination until the
+    /// searches meet.
+    ///
+    /// `forward` returns the neighbors of a state and the distance to them,
+    /// as for [ShortestPath::find]. `backward` returns the states from which a
+    /// given state can be reached in one step, and the distance of that step.
+    /// In an undirected graph these are the same function.
+    pub fn find_bidirectional<FwdFn, Fwd, BackFn, Back>(
+        origin: &P,
+        destination: &P,
+        forward: FwdFn,
+        backward: BackFn,
+    ) -> Option<Self>
+    where
+        FwdFn: FnMut(&P) -> Fwd,
+        Fwd: IntoIterator<Item = (P, D)>,
+        BackFn: FnMut(&P) -> Back,
+        Back: IntoIterator<Item = (P, D)>,
+    {
+        bidirectional::search(origin, destination, forward, backward)
+    }
+
+    /// Find the shortest path using iterative deepening A* search, which
+    /// keeps only the current path in memory, at the cost of revisiting
+    /// states.
+    ///
+    /// The arguments are the same as for [ShortestPath::find_astar]. The
+    /// estimate must never overestimate the remaining distance.
+    ///
+    /// In the returned [Stats], `max_frontier` is the longest path explored.
+    pub fn find_ida_star<NbrFn, Nbrs, EstFn>(
+        origin: &P,
+        estimate: EstFn,
+        neighbors: NbrFn,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+        EstFn: Fn(&P) -> D,
+    {
+        ida_star::search(origin, estimate, neighbors)
+    }
+
+    /// Find the shortest path from any of several origins to a destination,
+    /// using Djikstra's method.
+    ///
+    /// Each origin has an initial cost, which counts towards the distance of
+    /// paths from it; use `D::default()` if they're all equally good starting points.
+    ///
+    /// The origin of the returned path, from [ShortestPath::origin], is the
+    /// one that wins.
+    pub fn find_from_any<Origins, NbrFn, Nbrs, DestFn>(
+        origins: Origins,
+        is_destination: DestFn,
+        neighbors: NbrFn,
+    ) -> Option<Self>
+    where
+        Origins: IntoIterator<Item = (P, D)>,
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+        DestFn: Fn(&P) -> bool,
+    {
+        search(
+            origins,
+            is_destination,
+            |_| D::default(),
+            neighbors,
+            &mut (),
+        )
     }
 
     /// Return the total distance along the shortest path.
@@ -209,7 +315,705 @@ where
         self.path.last().expect("path is not empty")
     }
 
+    /// Return the point where the path starts.
+    ///
+    /// This is useful when the search started from several origins.
+    pub fn origin(&self) -> &P {
+        self.path.first().expect("path is not empty")
+    }
+
     pub fn stats(&self) -> &Stats {
         &self.stats
     }
 }
+
+impl<P> ShortestPath<P, usize>
+where
+    P: Eq + Clone + Hash + Debug,
+{
+    /// Find the shortest path in a graph where every step has cost 1, by
+    /// breadth-first search.
+    ///
+    /// `neighbors` returns the states reachable in one step from a given state.
+    pub fn find_bfs<NbrFn, Nbrs, DestFn>(
+        origin: &P,
+        is_destination: DestFn,
+        neighbors: NbrFn,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = P>,
+        DestFn: Fn(&P) -> bool,
+    {
+        Self::find_bfs_with_observer(origin, is_destination, neighbors, &mut ())
+    }
+
+    /// Find the shortest path by breadth-first search, reporting progress to an [Observer].
+    pub fn find_bfs_with_observer<NbrFn, Nbrs, DestFn, Obs>(
+        origin: &P,
+        is_destination: DestFn,
+        mut neighbors: NbrFn,
+        observer: &mut Obs,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = P>,
+        DestFn: Fn(&P) -> bool,
+        Obs: Observer<P, usize>,
+    {
+        bfs::search_01(
+            [origin.clone()],
+            is_destination,
+            |p| neighbors(p).into_iter().map(|q| (q, 1)),
+            HashMap::new(),
+            observer,
+        )
+    }
+
+    /// Find the shortest path in a graph where every step costs either 0 or 1,
+    /// using a double-ended queue rather than a heap.
+    ///
+    /// `neighbors` is as for [ShortestPath::find], but every step cost must be 0 or 1.
+    pub fn find_01<NbrFn, Nbrs, DestFn>(
+        origin: &P,
+        is_destination: DestFn,
+        neighbors: NbrFn,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, usize)>,
+        DestFn: Fn(&P) -> bool,
+    {
+        Self::find_01_with_observer(origin, is_destination, neighbors, &mut ())
+    }
+
+    /// Find the shortest path where every step costs 0 or 1, reporting
+    /// progress to an [Observer].
+    pub fn find_01_with_observer<NbrFn, Nbrs, DestFn, Obs>(
+        origin: &P,
+        is_destination: DestFn,
+        neighbors: NbrFn,
+        observer: &mut Obs,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, usize)>,
+        DestFn: Fn(&P) -> bool,
+        Obs: Observer<P, usize>,
+    {
+        bfs::search_01(
+            [origin.clone()],
+            is_destination,
+            neighbors,
+            HashMap::new(),
+            observer,
+        )
+    }
+}
+
+/// Search outwards from `origins`, in order of distance so far plus the
+/// estimated remaining distance, until reaching a destination.
+fn search<P, D, Origins, NbrFn, Nbrs, DestFn, EstFn, Obs>(
+    origins: Origins,
+    is_destination: DestFn,
+    estimate: EstFn,
+    mut neighbors: NbrFn,
+    observer: &mut Obs,
+) -> Option<ShortestPath<P, D>>
+where
+    P: Eq + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+    Origins: IntoIterator<Item = (P, D)>,
+    NbrFn: FnMut(&P) -> Nbrs,
+    Nbrs: IntoIterator<Item = (P, D)>,
+    DestFn: Fn(&P) -> bool,
+    EstFn: Fn(&P) -> D,
+    Obs: Observer<P, D>,
+{
+    let start_time = Instant::now();
+    let mut stats = Stats::default();
+    // Next points to visit, ordered by estimated total distance, and then by
+    // distance so far.
+    let mut queue = KeyedHeap::<P, (D, D)>::new();
+    // Shortest known distance to reach any point.
+    let mut best = HashMap::<P, D>::new();
+    // The previous state that leads, on the best path, to this state.
+    let mut predecessor = HashMap::<P, P>::new();
+    for (origin, d) in origins {
+        if best.get(&origin).is_some_and(|prev_d| *prev_d <= d) {
+            continue;
+        }
+        queue.push(origin.clone(), (d.clone() + estimate(&origin), d.clone()));
+        best.insert(origin, d);
+    }
+    while let Some((p, (_priority, d))) = queue.pop() {
+        stats.search_cycles += 1;
+        if d > best[&p] {
+            // A shorter path to p was found after this entry was pushed.
+            continue;
+        }
+        stats.states_examined += 1;
+        observer.on_pop(&p, &d);
+        if is_destination(&p) {
+            stats.elapsed = start_time.elapsed();
+            observer.on_goal(&p, &d, &stats);
+            // Reassemble (a) shortest path to the destination by looking backwards
+            // at the step that led to each point.
+            let mut path = vec![p];
+            while let Some(next) = predecessor.get(path.last().unwrap()) {
+                path.push(next.clone());
+            }
+            path.reverse();
+            return Some(ShortestPath {
+                distance: d,
+                path,
+                stats,
+            });
+        }
+        for (np, step) in neighbors(&p) {
+            let nd = step + d.clone();
+            if let Some(prev_d) = best.get(&np) {
+                if nd >= *prev_d {
+                    continue; // Already found a shorter path; don't revisit.
+                }
+                stats.duplicate_pushes += 1;
+            }
+            observer.on_relax(&p, &np, &nd);
+            best.insert(np.clone(), nd.clone());
+            queue.push(np.clone(), (nd.clone() + estimate(&np), nd));
+            predecessor.insert(np, p.clone());
+        }
+        stats.max_frontier = max(stats.max_frontier, queue.len());
+    }
+    stats.elapsed = start_time.elapsed();
+    observer.on_exhausted(&stats);
+    None
+}
+
+/// All the shortest paths from an origin to the nearest destinations.
+///
+/// Unlike [ShortestPath], this remembers every predecessor that reaches a
+/// state at its shortest distance, so together they form a directed acyclic
+/// graph of all the optimal paths. This can answer questions like "which
+/// points lie on any optimal path?" and "how many optimal paths are there?"
+pub struct AllShortestPaths<P, D>
+where
+    P: Eq + Ord + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+{
+    distance: D,
+    /// All destinations reached at the shortest distance.
+    destinations: Vec<P>,
+    /// Shortest known distance to every state reached by the search.
+    best: HashMap<P, D>,
+    /// For every state reached, all the states that lead to it at its shortest distance.
+    predecessors: HashMap<P, Vec<P>>,
+    stats: Stats,
+}
+
+impl<P, D> AllShortestPaths<P, D>
+where
+    P: Eq + Ord + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+{
+    /// Find all the shortest paths from `origin` to any destination.
+    ///
+    /// The arguments are the same as for [ShortestPath::find], except that every
+    /// step must have a cost greater than zero: otherwise, there might be
+    /// infinitely many optimal paths.
+    pub fn find<NbrFn, Nbrs, DestFn>(
+        origin: &P,
+        is_destination: DestFn,
+        mut neighbors: NbrFn,
+    ) -> Option<Self>
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+        DestFn: Fn(&P) -> bool,
+    {
+        let start_time = Instant::now();
+        let mut stats = Stats::default();
+        let mut queue = KeyedHeap::<P, D>::new();
+        let mut best = HashMap::<P, D>::new();
+        let mut predecessors = HashMap::<P, Vec<P>>::new();
+        let mut found: Option<(D, Vec<P>)> = None;
+        queue.push(origin.clone(), D::default());
+        best.insert(origin.clone(), D::default());
+        while let Some((p, d)) = queue.pop() {
+            stats.search_cycles += 1;
+            if let Some((goal_d, destinations)) = &mut found {
+                // Keep going only to collect other destinations at the same distance.
+                if d > *goal_d {
+                    break;
+                } else if is_destination(&p) && !destinations.contains(&p) {
+                    destinations.push(p);
+                }
+                continue;
+            }
+            if d > best[&p] {
+                continue;
+            }
+            stats.states_examined += 1;
+            if is_destination(&p) {
+                found = Some((d, vec![p]));
+                continue;
+            }
+            for (np, step) in neighbors(&p) {
+                debug_assert!(step > D::default(), "step cost must be positive");
+                let nd = step + d.clone();
+                match best.get(&np) {
+                    Some(prev_d) if nd > *prev_d => continue,
+                    Some(prev_d) if nd == *prev_d => {
+                        let preds = predecessors.entry(np).or_default();
+                        if !preds.contains(&p) {
+                            preds.push(p.clone());
+                        }
+                        continue;
+                    }
+                    Some(_) => stats.duplicate_pushes += 1,
+                    None => (),
+                }
+                best.insert(np.clone(), nd.clone());
+                predecessors.insert(np.clone(), vec![p.clone()]);
+                queue.push(np, nd);
+            }
+            stats.max_frontier = max(stats.max_frontier, queue.len());
+        }
+        stats.elapsed = start_time.elapsed();
+        let (distance, destinations) = found?;
+        Some(AllShortestPaths {
+            distance,
+            destinations,
+            best,
+            predecessors,
+            stats,
+        })
+    }
+
+    /// Return the total distance along each of the shortest paths.
+    pub fn distance(&self) -> D {
+        self.distance.clone()
+    }
+
+    /// Return all the destinations that are reached at the shortest distance.
+    pub fn destinations(&self) -> &[P] {
+        &self.destinations
+    }
+
+    /// Return the states from which there's an optimal step to `p`, or
+    /// an empty slice for the origin or states that weren't reached.
+    pub fn predecessors(&self, p: &P) -> &[P] {
+        self.predecessors.get(p).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return every state that lies on at least one shortest path to a destination,
+    /// including the origin and the destinations.
+    pub fn points_on_paths(&self) -> HashSet<P> {
+        let mut seen: HashSet<P> = self.destinations.iter().cloned().collect();
+        let mut stack: Vec<P> = self.destinations.clone();
+        while let Some(p) = stack.pop() {
+            for q in self.predecessors(&p) {
+                if seen.insert(q.clone()) {
+                    stack.push(q.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    /// Return the edges of the graph of optimal paths, as `(from, to)` pairs.
+    pub fn edges(&self) -> Vec<(P, P)> {
+        let mut edges: Vec<(P, P)> = self
+            .points_on_paths()
+            .into_iter()
+            .flat_map(|p| {
+                self.predecessors(&p)
+                    .iter()
+                    .map(move |q| (q.clone(), p.clone()))
+            })
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Count the distinct shortest paths to all destinations.
+    ///
+    /// The count saturates at `u128::MAX` rather than overflowing.
+    pub fn count_paths(&self) -> u128 {
+        // Every step has a positive cost, so visiting states in order of
+        // distance visits each one after all its predecessors.
+        let mut points: Vec<P> = self.points_on_paths().into_iter().collect();
+        points.sort_by(|a, b| self.best[a].cmp(&self.best[b]));
+        let mut counts = HashMap::<&P, u128>::new();
+        for p in &points {
+            let preds = self.predecessors(p);
+            let n = if preds.is_empty() {
+                1 // The origin.
+            } else {
+                preds
+                    .iter()
+                    .fold(0u128, |acc, q| acc.saturating_add(counts[q]))
+            };
+            counts.insert(p, n);
+        }
+        self.destinations
+            .iter()
+            .fold(0, |acc, d| acc.saturating_add(counts[d]))
+    }
+
+    /// Iterate every distinct shortest path, each as a list of states from
+    /// the origin to a destination.
+    ///
+    /// There may be exponentially many paths: see [AllShortestPaths::count_paths].
+    pub fn paths(&self) -> impl Iterator<Item = Vec<P>> + '_ {
+        // Partial paths, from some state back to a destination, in reverse order.
+        let mut stack: Vec<Vec<P>> = self.destinations.iter().map(|d| vec![d.clone()]).collect();
+        std::iter::from_fn(move || {
+            while let Some(mut rev) = stack.pop() {
+                let preds = self.predecessors(rev.last().unwrap());
+                if let Some((first, rest)) = preds.split_first() {
+                    for q in rest {
+                        let mut alt = rev.clone();
+                        alt.push(q.clone());
+                        stack.push(alt);
+                    }
+                    rev.push(first.clone());
+                    stack.push(rev);
+                } else {
+                    rev.reverse();
+                    return Some(rev);
+                }
+            }
+            None
+        })
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Walk along a line of integers, where each step costs 1 and a
+    /// shortcut jumps from 2 to 7 for cost 2.
+    fn line_neighbors(p: &isize) -> Vec<(isize, usize)> {
+        let mut v = vec![(p - 1, 1), (p + 1, 1)];
+        if *p == 2 {
+            v.push((7, 2));
+        }
+        v
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        pops: Vec<isize>,
+        relaxed: usize,
+        goal: Option<(isize, usize)>,
+        exhausted: bool,
+    }
+
+    impl Observer<isize, usize> for Recorder {
+        fn on_pop(&mut self, p: &isize, _d: &usize) {
+            self.pops.push(*p);
+        }
+
+        fn on_relax(&mut self, _from: &isize, _to: &isize, _d: &usize) {
+            self.relaxed += 1;
+        }
+
+        fn on_goal(&mut self, p: &isize, d: &usize, _stats: &Stats) {
+            self.goal = Some((*p, *d));
+        }
+
+        fn on_exhausted(&mut self, _stats: &Stats) {
+            self.exhausted = true;
+        }
+    }
+
+    #[test]
+    fn find_takes_shortcut() {
+        let path = ShortestPath::find(&0, |p| *p == 8, line_neighbors).unwrap();
+        assert_eq!(path.distance(), 5);
+        assert_eq!(path.path().copied().collect::<Vec<_>>(), [0, 1, 2, 7, 8]);
+        assert_eq!(*path.final_point(), 8);
+        let stats = path.stats();
+        assert!(stats.states_examined <= stats.search_cycles);
+        assert!(stats.max_frontier > 0);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let path = ShortestPath::find_astar(
+            &0,
+            |p: &isize| (8 - p).unsigned_abs().div_ceil(2),
+            line_neighbors,
+        )
+        .unwrap();
+        assert_eq!(path.distance(), 5);
+        assert_eq!(path.path().copied().collect::<Vec<_>>(), [0, 1, 2, 7, 8]);
+    }
+
+    #[test]
+    fn nearest_of_several_origins() {
+        let path =
+            ShortestPath::find_from_any([(0, 0), (10, 0)], |p| *p == 8, line_neighbors).unwrap();
+        assert_eq!(path.distance(), 2);
+        assert_eq!(*path.origin(), 10);
+        // The initial cost of an origin counts towards the distance.
+        let path =
+            ShortestPath::find_from_any([(0, 0), (10, 4)], |p| *p == 8, line_neighbors).unwrap();
+        assert_eq!(path.distance(), 5);
+        assert_eq!(*path.origin(), 0);
+        // The path reports which of several destinations was nearest.
+        let path =
+            ShortestPath::find_from_any([(0, 0), (10, 4)], |p| *p == 8 || *p == -3, line_neighbors)
+                .unwrap();
+        assert_eq!((path.origin(), path.final_point()), (&0, &-3));
+    }
+
+    /// The reverse of [line_neighbors]: the states that lead to `p`.
+    fn line_predecessors(p: &isize) -> Vec<(isize, usize)> {
+        let mut v = vec![(p - 1, 1), (p + 1, 1)];
+        if *p == 7 {
+            v.push((2, 2));
+        }
+        v
+    }
+
+    #[test]
+    fn bidirectional_takes_shortcut() {
+        let path =
+            ShortestPath::find_bidirectional(&0, &8, line_neighbors, line_predecessors).unwrap();
+        assert_eq!(path.distance(), 5);
+        assert_eq!(path.path().copied().collect::<Vec<_>>(), [0, 1, 2, 7, 8]);
+        // The shortcut only goes one way.
+        let path =
+            ShortestPath::find_bidirectional(&8, &0, line_neighbors, line_predecessors).unwrap();
+        assert_eq!(path.distance(), 8);
+        assert_eq!(path.path().count(), 9);
+        let path =
+            ShortestPath::find_bidirectional(&3, &3, line_neighbors, line_predecessors).unwrap();
+        assert_eq!(path.distance(), 0);
+        assert_eq!(path.path().copied().collect::<Vec<_>>(), [3]);
+    }
+
+    #[test]
+    fn bidirectional_unreachable() {
+        let bounded = |p: &isize| {
+            line_neighbors(p)
+                .into_iter()
+                .filter(|(q, _)| (0..5).contains(q))
+                .collect::<Vec<_>>()
+        };
+        let bounded_preds = |p: &isize| {
+            line_predecessors(p)
+                .into_iter()
+                .filter(|(q, _)| (0..5).contains(q))
+                .collect::<Vec<_>>()
+        };
+        assert!(ShortestPath::find_bidirectional(&0, &9, bounded, bounded_preds).is_none());
+    }
+
+    #[test]
+    fn ida_star_takes_shortcut() {
+        let path = ShortestPath::find_ida_star(
+            &0,
+            |p: &isize| (8 - p).unsigned_abs().div_ceil(2),
+            line_neighbors,
+        )
+        .unwrap();
+        assert_eq!(path.distance(), 5);
+        assert_eq!(path.path().copied().collect::<Vec<_>>(), [0, 1, 2, 7, 8]);
+        assert_eq!(path.stats().max_frontier, 5);
+    }
+
+    #[test]
+    fn ida_star_exhausted() {
+        let bounded = |p: &isize| {
+            line_neighbors(p)
+                .into_iter()
+                .filter(|(q, _)| (0..5).contains(q))
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            ShortestPath::find_ida_star(&0, |p: &isize| (9 - p).unsigned_abs(), bounded).is_none()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn searches_agree_on_weighted_grid(costs in prop::collection::vec(1u32..5, 36)) {
+            // Entering a cell of a 6x6 grid costs the weight of that cell.
+            let cost = |x: i32, y: i32| costs[(y * 6 + x) as usize];
+            let nbrs = |&(x, y): &(i32, i32)| {
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(|&(x, y)| (0..6).contains(&x) && (0..6).contains(&y))
+                    .map(|(x, y)| ((x, y), cost(x, y)))
+                    .collect::<Vec<_>>()
+            };
+            // Undirected costs are the cost of leaving each cell.
+            let preds = |&(x, y): &(i32, i32)| {
+                nbrs(&(x, y))
+                    .into_iter()
+                    .map(|(q, _)| (q, cost(x, y)))
+                    .collect::<Vec<_>>()
+            };
+            let end = (5, 5);
+            let estimate = |&(x, y): &(i32, i32)| (5 - x + 5 - y) as u32;
+            let dijkstra = ShortestPath::find(&(0, 0), |p| *p == end, nbrs).unwrap();
+            let bidi = ShortestPath::find_bidirectional(&(0, 0), &end, nbrs, preds).unwrap();
+            let ida = ShortestPath::find_ida_star(&(0, 0), estimate, nbrs).unwrap();
+            prop_assert_eq!(dijkstra.distance(), bidi.distance());
+            prop_assert_eq!(dijkstra.distance(), ida.distance());
+            for path in [&bidi, &ida] {
+                let steps: u32 = path.path().skip(1).map(|&(x, y)| cost(x, y)).sum();
+                prop_assert_eq!(steps, path.distance());
+                prop_assert_eq!(path.origin(), &(0, 0));
+                prop_assert_eq!(path.final_point(), &end);
+            }
+        }
+    }
+
+    #[test]
+    fn observer_sees_events() {
+        let mut rec = Recorder::default();
+        let path =
+            ShortestPath::find_with_observer(&0, |p| *p == 3, line_neighbors, &mut rec).unwrap();
+        assert_eq!(rec.pops.first(), Some(&0));
+        assert_eq!(rec.pops.last(), Some(&3));
+        assert_eq!(rec.pops.len(), path.stats().states_examined);
+        assert!(rec.relaxed >= 3);
+        assert_eq!(rec.goal, Some((3, 3)));
+        assert!(!rec.exhausted);
+    }
+
+    #[test]
+    fn exhausted_search() {
+        let mut rec = Recorder::default();
+        let bounded = |p: &isize| {
+            line_neighbors(p)
+                .into_iter()
+                .filter(|(q, _)| (0..5).contains(q))
+                .collect::<Vec<_>>()
+        };
+        assert!(ShortestPath::find_with_observer(&0, |p| *p == 9, bounded, &mut rec).is_none());
+        assert!(rec.exhausted);
+        assert_eq!(rec.goal, None);
+        let mut sorted = rec.pops.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bfs_observer_sees_events() {
+        let mut rec = Recorder::default();
+        let steps = |p: &isize| [p - 1, p + 1];
+        let path = ShortestPath::find_bfs_with_observer(&0, |p| *p == 3, steps, &mut rec).unwrap();
+        assert_eq!(rec.pops.first(), Some(&0));
+        assert_eq!(rec.pops.len(), path.stats().states_examined);
+        assert_eq!(rec.goal, Some((3, 3)));
+        assert!(!rec.exhausted);
+
+        let mut rec = Recorder::default();
+        let bounded = |p: &isize| (*p < 5).then_some((p + 1, 1));
+        assert!(ShortestPath::find_01_with_observer(&0, |p| *p == 9, bounded, &mut rec).is_none());
+        assert!(rec.exhausted);
+        assert_eq!(rec.pops, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(rec.relaxed, 5);
+    }
+
+    /// Moves right or down on a grid, to count lattice paths.
+    fn grid_neighbors(&(x, y): &(u32, u32)) -> Vec<((u32, u32), u32)> {
+        vec![((x + 1, y), 1), ((x, y + 1), 1)]
+    }
+
+    #[test]
+    fn all_paths_on_grid() {
+        let all = AllShortestPaths::find(&(0, 0), |p| *p == (2, 2), grid_neighbors).unwrap();
+        assert_eq!(all.distance(), 4);
+        assert_eq!(all.destinations(), [(2, 2)]);
+        // Choose 2 of 4 steps to go right.
+        assert_eq!(all.count_paths(), 6);
+        let mut paths: Vec<Vec<(u32, u32)>> = all.paths().collect();
+        assert_eq!(paths.len(), 6);
+        paths.sort();
+        paths.dedup();
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|p| p.len() == 5 && p[0] == (0, 0)));
+        assert_eq!(all.points_on_paths().len(), 9);
+        assert_eq!(all.edges().len(), 12);
+        let mut preds = all.predecessors(&(1, 1)).to_vec();
+        preds.sort_unstable();
+        assert_eq!(preds, [(0, 1), (1, 0)]);
+        assert!(all.predecessors(&(0, 0)).is_empty());
+    }
+
+    #[test]
+    fn all_paths_excludes_detours() {
+        // Two routes of cost 2 from 0 to 3, and a slower one through 4.
+        let nbrs = |p: &u8| match p {
+            0 => vec![(1, 1), (2, 1), (4, 1)],
+            1 | 2 => vec![(3, 1)],
+            4 => vec![(3, 5)],
+            _ => vec![],
+        };
+        let all = AllShortestPaths::find(&0u8, |p| *p == 3, nbrs).unwrap();
+        assert_eq!(all.count_paths(), 2);
+        let points = all.points_on_paths();
+        assert!(!points.contains(&4));
+        assert_eq!(points.len(), 4);
+    }
+
+    #[test]
+    fn all_paths_to_several_destinations() {
+        let all = AllShortestPaths::find(&(0, 0), |(x, y)| x + y == 2, grid_neighbors).unwrap();
+        let mut dests = all.destinations().to_vec();
+        dests.sort_unstable();
+        assert_eq!(dests, [(0, 2), (1, 1), (2, 0)]);
+        assert_eq!(all.count_paths(), 4);
+    }
+
+    #[test]
+    fn path_count_saturates() {
+        let all = AllShortestPaths::find(&(0, 0), |p| *p == (80, 80), grid_neighbors).unwrap();
+        // C(160, 80) is about 9e46, which doesn't fit in u128.
+        assert_eq!(all.count_paths(), u128::MAX);
+    }
+
+    #[test]
+    fn duplicate_pushes_are_counted() {
+        // 0 -> 2 directly costs 10, but via 1 costs 2, so 2 is pushed twice.
+        let nbrs = |p: &u8| match p {
+            0 => vec![(2, 10), (1, 1)],
+            1 => vec![(2, 1)],
+            _ => vec![],
+        };
+        let path = ShortestPath::find(&0u8, |p| *p == 2, nbrs).unwrap();
+        assert_eq!(path.distance(), 2usize);
+        assert_eq!(path.stats().duplicate_pushes, 1);
+    }
+
+    #[test]
+    fn states_need_not_be_ordered() {
+        // A state type with no ordering: the search only needs to hash states.
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        struct Room(&'static str);
+        let nbrs = |r: &Room| match r.0 {
+            "hall" => vec![(Room("kitchen"), 2), (Room("study"), 1)],
+            "study" => vec![(Room("kitchen"), 3)],
+            _ => vec![],
+        };
+        let path = ShortestPath::find(&Room("hall"), |r| r.0 == "kitchen", nbrs).unwrap();
+        assert_eq!(path.distance(), 2);
+        assert_eq!(
+            path.path().cloned().collect::<Vec<_>>(),
+            [Room("hall"), Room("kitchen")]
+        );
+    }
+}
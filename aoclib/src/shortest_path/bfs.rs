@@ -0,0 +1,250 @@
+// Copyright 2026 Martin Pool
+
+//! Shortest paths where every step costs 0 or 1, by breadth-first search
+//! on a deque rather than Djikstra's method on a heap.
+
+use std::cmp::max;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::time::Instant;
+
+use super::{Observer, ShortestPath, Stats};
+use crate::{point, Point};
+
+/// The distance to, and predecessor of, every state reached by a search.
+pub(crate) trait StateTable<P> {
+    /// The shortest known distance to `p`, if it's been reached.
+    fn distance(&self, p: &P) -> Option<usize>;
+
+    /// The state before `p` on the shortest known path to it.
+    fn predecessor(&self, p: &P) -> Option<P>;
+
+    /// Record a new shortest known path to `p`.
+    fn set(&mut self, p: &P, distance: usize, predecessor: Option<P>);
+}
+
+impl<P: Eq + Hash + Clone> StateTable<P> for HashMap<P, (usize, Option<P>)> {
+    fn distance(&self, p: &P) -> Option<usize> {
+        self.get(p).map(|(d, _)| *d)
+    }
+
+    fn predecessor(&self, p: &P) -> Option<P> {
+        self.get(p).and_then(|(_, pred)| pred.clone())
+    }
+
+    fn set(&mut self, p: &P, distance: usize, predecessor: Option<P>) {
+        self.insert(p.clone(), (distance, predecessor));
+    }
+}
+
+/// A [StateTable] for points within a `w` by `h` rectangle from the origin,
+/// such as a [crate::Matrix], stored in a flat vector rather than hashed.
+pub(crate) struct DenseTable {
+    w: usize,
+    h: usize,
+    /// Distance and predecessor index for each point, in row-major order.
+    cells: Vec<Option<(usize, Option<usize>)>>,
+}
+
+impl DenseTable {
+    pub(crate) fn new(w: usize, h: usize) -> DenseTable {
+        DenseTable {
+            w,
+            h,
+            cells: vec![None; w * h],
+        }
+    }
+
+    fn index(&self, p: &Point) -> usize {
+        assert!(
+            p.x >= 0 && p.y >= 0 && (p.x as usize) < self.w && (p.y as usize) < self.h,
+            "{p:?} is outside {}x{} table",
+            self.w,
+            self.h
+        );
+        p.y as usize * self.w + p.x as usize
+    }
+}
+
+impl StateTable<Point> for DenseTable {
+    fn distance(&self, p: &Point) -> Option<usize> {
+        self.cells[self.index(p)].map(|(d, _)| d)
+    }
+
+    fn predecessor(&self, p: &Point) -> Option<Point> {
+        self.cells[self.index(p)]
+            .and_then(|(_, pred)| pred)
+            .map(|i| point((i % self.w) as isize, (i / self.w) as isize))
+    }
+
+    fn set(&mut self, p: &Point, distance: usize, predecessor: Option<Point>) {
+        let pred = predecessor.map(|q| self.index(&q));
+        let i = self.index(p);
+        self.cells[i] = Some((distance, pred));
+    }
+}
+
+/// Search outwards from `origins` until reaching a destination, where every
+/// step costs either 0 or 1.
+///
+/// Steps costing 0 go to the front of the queue and steps costing 1 to the
+/// back, so states are taken from the queue in order of distance, just as
+/// from a heap. If every step costs 1 this is plain breadth-first search,
+/// and each state is queued only once.
+pub(crate) fn search_01<P, T, Origins, NbrFn, Nbrs, DestFn, Obs>(
+    origins: Origins,
+    is_destination: DestFn,
+    mut neighbors: NbrFn,
+    mut table: T,
+    observer: &mut Obs,
+) -> Option<ShortestPath<P, usize>>
+where
+    P: Eq + Clone + Hash + std::fmt::Debug,
+    T: StateTable<P>,
+    Origins: IntoIterator<Item = P>,
+    NbrFn: FnMut(&P) -> Nbrs,
+    Nbrs: IntoIterator<Item = (P, usize)>,
+    DestFn: Fn(&P) -> bool,
+    Obs: Observer<P, usize>,
+{
+    let start_time = Instant::now();
+    let mut stats = Stats::default();
+    let mut queue = VecDeque::new();
+    for origin in origins {
+        if table.distance(&origin).is_none() {
+            table.set(&origin, 0, None);
+            queue.push_back((0, origin));
+        }
+    }
+    while let Some((d, p)) = queue.pop_front() {
+        stats.search_cycles += 1;
+        if table.distance(&p).is_some_and(|best| d > best) {
+            // A shorter path to p was found after this entry was pushed.
+            continue;
+        }
+        stats.states_examined += 1;
+        observer.on_pop(&p, &d);
+        if is_destination(&p) {
+            stats.elapsed = start_time.elapsed();
+            observer.on_goal(&p, &d, &stats);
+            let mut path = vec![p];
+            while let Some(prev) = table.predecessor(path.last().unwrap()) {
+                path.push(prev);
+            }
+            path.reverse();
+            return Some(ShortestPath {
+                distance: d,
+                path,
+                stats,
+            });
+        }
+        for (np, step) in neighbors(&p) {
+            debug_assert!(step <= 1, "step cost {step} is not 0 or 1");
+            let nd = d + step;
+            if let Some(prev_d) = table.distance(&np) {
+                if nd >= prev_d {
+                    continue;
+                }
+                stats.duplicate_pushes += 1;
+            }
+            observer.on_relax(&p, &np, &nd);
+            table.set(&np, nd, Some(p.clone()));
+            if step == 0 {
+                queue.push_front((nd, np));
+            } else {
+                queue.push_back((nd, np));
+            }
+        }
+        stats.max_frontier = max(stats.max_frontier, queue.len());
+    }
+    stats.elapsed = start_time.elapsed();
+    observer.on_exhausted(&stats);
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::Matrix;
+
+    /// Moves between neighboring open cells in a grid of walls.
+    fn open_moves(m: &Matrix<bool>, p: Point) -> Vec<Point> {
+        m.neighbors4(p)
+            .filter(|(_q, wall)| !**wall)
+            .map(|(q, _)| q)
+            .collect()
+    }
+
+    #[test]
+    fn zero_cost_steps_are_taken_first() {
+        // Walking right costs 1, but there's a free teleporter from 0 to 9.
+        let nbrs = |p: &i32| {
+            let mut v = vec![(p + 1, 1)];
+            if *p == 0 {
+                v.push((9, 0));
+            }
+            v
+        };
+        let path = ShortestPath::find_01(&0, |p| *p == 10, nbrs).unwrap();
+        assert_eq!(path.distance(), 1);
+        assert_eq!(path.path().copied().collect::<Vec<_>>(), [0, 9, 10]);
+    }
+
+    #[test]
+    fn dense_table_round_trips_points() {
+        let mut t = DenseTable::new(3, 2);
+        assert_eq!(t.distance(&point(2, 1)), None);
+        t.set(&point(2, 1), 7, Some(point(1, 1)));
+        assert_eq!(t.distance(&point(2, 1)), Some(7));
+        assert_eq!(t.predecessor(&point(2, 1)), Some(point(1, 1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn dense_table_rejects_outside_points() {
+        DenseTable::new(3, 2).distance(&point(3, 0));
+    }
+
+    proptest! {
+        #[test]
+        fn bfs_agrees_with_djikstra(walls in prop::collection::vec(prop::bool::weighted(0.3), 64)) {
+            let mut m = Matrix::new(8, 8, false);
+            for (i, wall) in walls.into_iter().enumerate() {
+                m[point((i % 8) as isize, (i / 8) as isize)] = wall;
+            }
+            let (start, end) = (point(0, 0), point(7, 7));
+            m[start] = false;
+            m[end] = false;
+            let djikstra = ShortestPath::find(&start, |p| *p == end, |p| {
+                open_moves(&m, *p).into_iter().map(|q| (q, 1)).collect::<Vec<_>>()
+            });
+            let bfs = ShortestPath::find_bfs(&start, |p| *p == end, |p| open_moves(&m, *p));
+            let dense = m.shortest_path(start, end, |_, b| !b);
+            prop_assert_eq!(djikstra.as_ref().map(|p| p.distance()), bfs.as_ref().map(|p| p.distance()));
+            prop_assert_eq!(djikstra.as_ref().map(|p| p.distance()), dense.as_ref().map(|p| p.distance()));
+            if let Some(path) = dense {
+                prop_assert_eq!(path.path().count(), path.distance() + 1);
+                prop_assert_eq!(path.path().next(), Some(&start));
+                prop_assert_eq!(path.final_point(), &end);
+            }
+        }
+
+        #[test]
+        fn zero_one_agrees_with_djikstra(costs in prop::collection::vec(0usize..2, 64)) {
+            // Entering a cell costs whatever is written in it.
+            let mut m = Matrix::new(8, 8, 0usize);
+            for (i, c) in costs.into_iter().enumerate() {
+                m[point((i % 8) as isize, (i / 8) as isize)] = c;
+            }
+            let nbrs = |p: &Point| m.neighbors4(*p).map(|(q, c)| (q, *c)).collect::<Vec<_>>();
+            let end = point(7, 7);
+            let djikstra = ShortestPath::find(&point(0, 0), |p| *p == end, nbrs).unwrap();
+            let zero_one = ShortestPath::find_01(&point(0, 0), |p| *p == end, nbrs).unwrap();
+            let dense = m.shortest_path_01(point(0, 0), end, |_, c| Some(*c)).unwrap();
+            prop_assert_eq!(djikstra.distance(), zero_one.distance());
+            prop_assert_eq!(djikstra.distance(), dense.distance());
+        }
+    }
+}
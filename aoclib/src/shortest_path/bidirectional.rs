@@ -0,0 +1,132 @@
+// Copyright 2026 Martin Pool
+
+//! Djikstra's method searching from both ends at once.
+
+use std::cmp::max;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+use std::time::Instant;
+
+use super::{ShortestPath, Stats};
+use crate::KeyedHeap;
+
+/// One direction of a bidirectional search.
+struct Frontier<P, D>
+where
+    D: Ord,
+{
+    queue: KeyedHeap<P, D>,
+    best: HashMap<P, D>,
+    predecessor: HashMap<P, P>,
+}
+
+impl<P, D> Frontier<P, D>
+where
+    P: Eq + Clone + Hash,
+    D: Ord + Clone,
+{
+    fn new(start: &P, zero: D) -> Self {
+        let mut queue = KeyedHeap::new();
+        queue.push(start.clone(), zero.clone());
+        Frontier {
+            queue,
+            best: HashMap::from([(start.clone(), zero)]),
+            predecessor: HashMap::new(),
+        }
+    }
+
+    /// The states from `p` back to the start of this frontier, including both.
+    fn path_back(&self, p: &P) -> Vec<P> {
+        let mut path = vec![p.clone()];
+        while let Some(prev) = self.predecessor.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path
+    }
+}
+
+/// Search forwards from `origin` and backwards from `destination`, always
+/// expanding whichever side has the nearer frontier, until the two searches
+/// meet at the shortest path.
+///
+/// `backward` returns the states that can reach a given state in one step,
+/// and the cost of that step.
+pub(crate) fn search<P, D, FwdFn, Fwd, BackFn, Back>(
+    origin: &P,
+    destination: &P,
+    mut forward: FwdFn,
+    mut backward: BackFn,
+) -> Option<ShortestPath<P, D>>
+where
+    P: Eq + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+    FwdFn: FnMut(&P) -> Fwd,
+    Fwd: IntoIterator<Item = (P, D)>,
+    BackFn: FnMut(&P) -> Back,
+    Back: IntoIterator<Item = (P, D)>,
+{
+    let start_time = Instant::now();
+    let mut stats = Stats::default();
+    let mut fwd = Frontier::new(origin, D::default());
+    let mut back = Frontier::new(destination, D::default());
+    // The shortest complete path found so far, and the state where the two
+    // halves of it meet.
+    let mut found: Option<(D, P)> = (origin == destination).then(|| (D::default(), origin.clone()));
+    // When either side runs out of states, there are no more paths to find.
+    while let (Some((_, df)), Some((_, db))) = (fwd.queue.peek(), back.queue.peek()) {
+        if let Some((best, _)) = &found {
+            // Every path not yet found is at least this long.
+            if df.clone() + db.clone() >= *best {
+                break;
+            }
+        }
+        let forwards = df <= db;
+        let (this, other) = if forwards {
+            (&mut fwd, &back)
+        } else {
+            (&mut back, &fwd)
+        };
+        let (p, d) = this.queue.pop().unwrap();
+        stats.search_cycles += 1;
+        if d > this.best[&p] {
+            continue;
+        }
+        stats.states_examined += 1;
+        let nbrs: Vec<(P, D)> = if forwards {
+            forward(&p).into_iter().collect()
+        } else {
+            backward(&p).into_iter().collect()
+        };
+        for (np, step) in nbrs {
+            let nd = step + d.clone();
+            if let Some(prev_d) = this.best.get(&np) {
+                if nd >= *prev_d {
+                    continue;
+                }
+                stats.duplicate_pushes += 1;
+            }
+            if let Some(od) = other.best.get(&np) {
+                let total = nd.clone() + od.clone();
+                if found.as_ref().is_none_or(|(best, _)| total < *best) {
+                    found = Some((total, np.clone()));
+                }
+            }
+            this.best.insert(np.clone(), nd.clone());
+            this.predecessor.insert(np.clone(), p.clone());
+            this.queue.push(np, nd);
+        }
+        stats.max_frontier = max(stats.max_frontier, fwd.queue.len() + back.queue.len());
+    }
+    stats.elapsed = start_time.elapsed();
+    let (distance, meet) = found?;
+    let mut path = fwd.path_back(&meet);
+    path.reverse();
+    path.extend(back.path_back(&meet).into_iter().skip(1));
+    Some(ShortestPath {
+        distance,
+        path,
+        stats,
+    })
+}
@@ -0,0 +1,324 @@
+// Copyright 2026 Martin Pool
+
+//! Shortest distances from one origin to everywhere reachable.
+
+use std::cmp::max;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+use std::time::Instant;
+
+use super::Stats;
+use crate::KeyedHeap;
+
+/// The shortest distance from an origin to every reachable state, and the
+/// tree of shortest paths back to the origin.
+///
+/// Unlike [super::ShortestPath] this doesn't stop at a destination, so one
+/// search can answer many questions about the same origin.
+#[derive(Clone, Debug)]
+pub struct DistanceMap<P, D> {
+    origin: P,
+    best: HashMap<P, D>,
+    /// The previous state on a shortest path to each state other than the origin.
+    predecessor: HashMap<P, P>,
+    stats: Stats,
+}
+
+impl<P, D> DistanceMap<P, D>
+where
+    P: Eq + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+{
+    /// Find the distance to every state reachable from `origin`, using
+    /// Djikstra's method.
+    ///
+    /// `neighbors` returns the neighbors of a state, and the incremental
+    /// distance to them, as for [super::ShortestPath::find].
+    pub fn from_origin<NbrFn, Nbrs>(origin: &P, neighbors: NbrFn) -> Self
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+    {
+        DistanceMap::explore(origin, None, neighbors)
+    }
+
+    /// Find the distance to every state no more than `max_distance` from
+    /// `origin`.
+    pub fn within<NbrFn, Nbrs>(origin: &P, max_distance: D, neighbors: NbrFn) -> Self
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+    {
+        DistanceMap::explore(origin, Some(max_distance), neighbors)
+    }
+
+    fn explore<NbrFn, Nbrs>(origin: &P, max_distance: Option<D>, mut neighbors: NbrFn) -> Self
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = (P, D)>,
+    {
+        let start_time = Instant::now();
+        let mut stats = Stats::default();
+        let mut queue = KeyedHeap::<P, D>::new();
+        let mut best = HashMap::<P, D>::new();
+        let mut predecessor = HashMap::<P, P>::new();
+        queue.push(origin.clone(), D::default());
+        best.insert(origin.clone(), D::default());
+        while let Some((p, d)) = queue.pop() {
+            stats.search_cycles += 1;
+            if d > best[&p] {
+                continue;
+            }
+            stats.states_examined += 1;
+            for (np, step) in neighbors(&p) {
+                let nd = step + d.clone();
+                if max_distance.as_ref().is_some_and(|m| nd > *m) {
+                    continue;
+                }
+                if let Some(prev_d) = best.get(&np) {
+                    if nd >= *prev_d {
+                        continue;
+                    }
+                    stats.duplicate_pushes += 1;
+                }
+                best.insert(np.clone(), nd.clone());
+                predecessor.insert(np.clone(), p.clone());
+                queue.push(np, nd);
+            }
+            stats.max_frontier = max(stats.max_frontier, queue.len());
+        }
+        stats.elapsed = start_time.elapsed();
+        DistanceMap {
+            origin: origin.clone(),
+            best,
+            predecessor,
+            stats,
+        }
+    }
+
+    /// Return the shortest distance to `p`, or None if it's not reachable.
+    pub fn distance(&self, p: &P) -> Option<D> {
+        self.best.get(p).cloned()
+    }
+
+    /// Return the states on a shortest path from the origin to `p`, including both.
+    pub fn path_to(&self, p: &P) -> Option<Vec<P>> {
+        if !self.best.contains_key(p) {
+            return None;
+        }
+        let mut path = vec![p.clone()];
+        while let Some(prev) = self.predecessor.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Iterate all reachable states and their distances, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&P, &D)> {
+        self.best.iter()
+    }
+
+    /// Consume the map and return just the distance to every reachable state.
+    pub fn into_distances(self) -> HashMap<P, D> {
+        self.best
+    }
+}
+
+impl<P> DistanceMap<P, usize>
+where
+    P: Eq + Clone + Hash,
+{
+    /// Find the number of steps to every state reachable from `origin`, by
+    /// breadth-first search, where every step has cost 1.
+    pub fn bfs<NbrFn, Nbrs>(origin: &P, neighbors: NbrFn) -> Self
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = P>,
+    {
+        bfs(origin, None, neighbors)
+    }
+
+    /// Find the number of steps to every state no more than `max_steps` from
+    /// `origin`, by breadth-first search.
+    pub fn bfs_within<NbrFn, Nbrs>(origin: &P, max_steps: usize, neighbors: NbrFn) -> Self
+    where
+        NbrFn: FnMut(&P) -> Nbrs,
+        Nbrs: IntoIterator<Item = P>,
+    {
+        bfs(origin, Some(max_steps), neighbors)
+    }
+}
+
+impl<P, D> DistanceMap<P, D>
+where
+    P: Eq + Clone + Hash,
+{
+    /// Return the state the search started from.
+    pub fn origin(&self) -> &P {
+        &self.origin
+    }
+
+    /// Return the state before `p` on a shortest path from the origin, or None
+    /// for the origin or unreachable states.
+    pub fn predecessor(&self, p: &P) -> Option<&P> {
+        self.predecessor.get(p)
+    }
+
+    /// True if `p` is reachable.
+    pub fn contains(&self, p: &P) -> bool {
+        self.best.contains_key(p)
+    }
+
+    /// Return the number of reachable states, including the origin.
+    pub fn len(&self) -> usize {
+        self.best.len()
+    }
+
+    /// True if no states were reached.
+    pub fn is_empty(&self) -> bool {
+        self.best.is_empty()
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+}
+
+fn bfs<P, NbrFn, Nbrs>(
+    origin: &P,
+    max_steps: Option<usize>,
+    mut neighbors: NbrFn,
+) -> DistanceMap<P, usize>
+where
+    P: Eq + Clone + Hash,
+    NbrFn: FnMut(&P) -> Nbrs,
+    Nbrs: IntoIterator<Item = P>,
+{
+    let start_time = Instant::now();
+    let mut stats = Stats::default();
+    let mut queue = VecDeque::new();
+    let mut best = HashMap::new();
+    let mut predecessor = HashMap::new();
+    queue.push_back((0, origin.clone()));
+    best.insert(origin.clone(), 0);
+    while let Some((d, p)) = queue.pop_front() {
+        stats.search_cycles += 1;
+        stats.states_examined += 1;
+        if max_steps.is_some_and(|m| d >= m) {
+            continue;
+        }
+        for np in neighbors(&p) {
+            if let Entry::Vacant(e) = best.entry(np.clone()) {
+                e.insert(d + 1);
+                predecessor.insert(np.clone(), p.clone());
+                queue.push_back((d + 1, np));
+            }
+        }
+        stats.max_frontier = max(stats.max_frontier, queue.len());
+    }
+    stats.elapsed = start_time.elapsed();
+    DistanceMap {
+        origin: origin.clone(),
+        best,
+        predecessor,
+        stats,
+    }
+}
+
+/// Find the shortest distance between every pair of a set of points of
+/// interest, for example to build a smaller graph of just those points.
+///
+/// The result has an entry `(a, b)` for every pair of distinct points where
+/// `b` is reachable from `a`. Paths may pass through other points of interest.
+pub fn pairwise_distances<P, D, NbrFn, Nbrs>(
+    points: &[P],
+    mut neighbors: NbrFn,
+) -> HashMap<(P, P), D>
+where
+    P: Eq + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+    NbrFn: FnMut(&P) -> Nbrs,
+    Nbrs: IntoIterator<Item = (P, D)>,
+{
+    let mut result = HashMap::new();
+    for a in points {
+        let dm = DistanceMap::from_origin(a, &mut neighbors);
+        for b in points {
+            if a != b {
+                if let Some(d) = dm.distance(b) {
+                    result.insert((a.clone(), b.clone()), d);
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Integers joined to their neighbors, where odd-numbered steps cost 2.
+    fn line_neighbors(p: &i32) -> Vec<(i32, u32)> {
+        let cost = |q: i32| if q % 2 == 0 { 1 } else { 2 };
+        vec![(p - 1, cost(p - 1)), (p + 1, cost(p + 1))]
+    }
+
+    #[test]
+    fn bounded_dijkstra() {
+        let dm = DistanceMap::within(&0, 6, line_neighbors);
+        assert_eq!(dm.distance(&0), Some(0));
+        assert_eq!(dm.distance(&2), Some(3));
+        assert_eq!(dm.distance(&-4), Some(6));
+        assert_eq!(dm.distance(&5), None);
+        assert_eq!(dm.len(), 9);
+        assert_eq!(dm.path_to(&3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(dm.path_to(&99), None);
+        assert_eq!(dm.predecessor(&-1), Some(&0));
+        assert_eq!(dm.predecessor(&0), None);
+        assert!(dm.iter().all(|(_p, d)| *d <= 6));
+    }
+
+    #[test]
+    fn unbounded_dijkstra_on_finite_graph() {
+        let nbrs = |p: &char| match p {
+            'a' => vec![('b', 5), ('c', 1)],
+            'c' => vec![('b', 1), ('d', 7)],
+            _ => vec![],
+        };
+        let dm = DistanceMap::from_origin(&'a', nbrs);
+        assert_eq!(dm.origin(), &'a');
+        let mut ds: Vec<(char, u32)> = dm.into_distances().into_iter().collect();
+        ds.sort_unstable();
+        assert_eq!(ds, [('a', 0), ('b', 2), ('c', 1), ('d', 8)]);
+    }
+
+    #[test]
+    fn bfs_on_grid() {
+        let nbrs = |&(x, y): &(i32, i32)| [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
+        let dm = DistanceMap::bfs_within(&(0, 0), 3, nbrs);
+        assert_eq!(dm.distance(&(1, 2)), Some(3));
+        assert_eq!(dm.distance(&(2, 2)), None);
+        // A diamond of radius 3.
+        assert_eq!(dm.len(), 25);
+        assert_eq!(dm.path_to(&(0, 3)).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn pairwise() {
+        let nbrs = |p: &i32| {
+            let mut v = line_neighbors(p);
+            v.retain(|(q, _)| (0..10).contains(q));
+            v
+        };
+        let ds = pairwise_distances(&[0, 2, 5], nbrs);
+        assert_eq!(ds.len(), 6);
+        assert_eq!(ds[&(0, 2)], 3);
+        assert_eq!(ds[&(2, 0)], 3);
+        assert_eq!(ds[&(2, 5)], 5);
+    }
+}
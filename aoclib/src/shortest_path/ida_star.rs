@@ -0,0 +1,113 @@
+// Copyright 2026 Martin Pool
+
+//! [Iterative deepening A*](https://en.wikipedia.org/wiki/Iterative_deepening_A*),
+//! which uses memory only in proportion to the length of the path.
+
+use std::cmp::{max, min};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+use std::time::Instant;
+
+use super::{ShortestPath, Stats};
+
+/// The outcome of searching below one state.
+enum Probe<D> {
+    /// The path now holds a path to the destination.
+    Found(D),
+    /// No destination is within the bound; this is the smallest estimated
+    /// total distance that exceeded it, if any.
+    Exceeded(Option<D>),
+}
+
+/// Repeatedly search depth-first from `origin`, abandoning any path whose
+/// distance plus estimated remaining distance exceeds a bound, and raising
+/// the bound to the smallest estimate that exceeded it until a destination
+/// is found.
+///
+/// States are revisited rather than remembered, except that a path never
+/// loops back through a state already on it.
+pub(crate) fn search<P, D, NbrFn, Nbrs, EstFn>(
+    origin: &P,
+    estimate: EstFn,
+    mut neighbors: NbrFn,
+) -> Option<ShortestPath<P, D>>
+where
+    P: Eq + Clone + Hash + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+    NbrFn: FnMut(&P) -> Nbrs,
+    Nbrs: IntoIterator<Item = (P, D)>,
+    EstFn: Fn(&P) -> D,
+{
+    let start_time = Instant::now();
+    let mut stats = Stats::default();
+    let mut bound = estimate(origin);
+    let mut path = vec![origin.clone()];
+    loop {
+        match probe(
+            &mut path,
+            D::default(),
+            &bound,
+            &estimate,
+            &mut neighbors,
+            &mut stats,
+        ) {
+            Probe::Found(distance) => {
+                stats.elapsed = start_time.elapsed();
+                return Some(ShortestPath {
+                    distance,
+                    path,
+                    stats,
+                });
+            }
+            Probe::Exceeded(Some(next)) => bound = next,
+            Probe::Exceeded(None) => return None,
+        }
+    }
+}
+
+/// Search depth-first below the last state on `path`, which is at distance `d`.
+fn probe<P, D, NbrFn, Nbrs, EstFn>(
+    path: &mut Vec<P>,
+    d: D,
+    bound: &D,
+    estimate: &EstFn,
+    neighbors: &mut NbrFn,
+    stats: &mut Stats,
+) -> Probe<D>
+where
+    P: Eq + Clone + Debug,
+    D: Ord + Add<Output = D> + Clone + Default + Debug,
+    NbrFn: FnMut(&P) -> Nbrs,
+    Nbrs: IntoIterator<Item = (P, D)>,
+    EstFn: Fn(&P) -> D,
+{
+    let p = path.last().unwrap().clone();
+    stats.search_cycles += 1;
+    stats.states_examined += 1;
+    stats.max_frontier = max(stats.max_frontier, path.len());
+    let h = estimate(&p);
+    let f = d.clone() + h.clone();
+    if f > *bound {
+        return Probe::Exceeded(Some(f));
+    }
+    if h == D::default() {
+        return Probe::Found(d);
+    }
+    let mut next_bound: Option<D> = None;
+    for (np, step) in neighbors(&p) {
+        if path.contains(&np) {
+            continue;
+        }
+        path.push(np);
+        match probe(path, d.clone() + step, bound, estimate, neighbors, stats) {
+            Probe::Found(distance) => return Probe::Found(distance),
+            Probe::Exceeded(Some(e)) => {
+                next_bound = Some(next_bound.map_or(e.clone(), |b| min(b, e)));
+            }
+            Probe::Exceeded(None) => (),
+        }
+        path.pop();
+    }
+    Probe::Exceeded(next_bound)
+}
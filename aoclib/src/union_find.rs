@@ -0,0 +1,344 @@
+// Copyright 2026 Martin Pool
+
+//! Disjoint sets, also known as union-find: track which items have been
+//! joined together into groups.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Disjoint sets of the integers `0..len()`.
+///
+/// Initially every item is in a set by itself. Joining sets uses path
+/// compression and union by size, so takes nearly constant time.
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind {
+    /// The parent of each item, or itself for the representative of a set.
+    parent: Vec<usize>,
+    /// The number of items in the set, for representatives only.
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// Make `n` separate sets, each holding one item.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Add a new item in a set by itself, and return it.
+    pub fn add(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(i);
+        self.size.push(1);
+        self.components += 1;
+        i
+    }
+
+    /// The number of items in all sets.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of distinct sets.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Return the representative item of the set containing `a`.
+    ///
+    /// Two items are in the same set exactly when they have the same representative.
+    pub fn find(&mut self, a: usize) -> usize {
+        let mut root = a;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut i = a;
+        while self.parent[i] != root {
+            let next = self.parent[i];
+            self.parent[i] = root;
+            i = next;
+        }
+        root
+    }
+
+    /// Join the sets containing `a` and `b`.
+    ///
+    /// Returns false if they were already in the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut ra, mut rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        if self.size[ra] < self.size[rb] {
+            (ra, rb) = (rb, ra);
+        }
+        self.parent[rb] = ra;
+        self.size[ra] += self.size[rb];
+        self.components -= 1;
+        true
+    }
+
+    /// True if `a` and `b` are in the same set.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of items in the set containing `a`.
+    pub fn size_of(&mut self, a: usize) -> usize {
+        let r = self.find(a);
+        self.size[r]
+    }
+
+    /// Return the size of every set, in no particular order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect()
+    }
+
+    /// Return the items in every set.
+    ///
+    /// Each set is sorted, and the sets are in order of their smallest item.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for i in 0..self.len() {
+            let r = self.find(i);
+            let c = *index_of_root.entry(r).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[c].push(i);
+        }
+        components
+    }
+
+    /// Check that the internal invariants are true.
+    ///
+    /// Panics on failure.
+    pub fn assert_valid(&self) {
+        let roots: Vec<usize> = (0..self.len()).filter(|&i| self.parent[i] == i).collect();
+        assert_eq!(roots.len(), self.components);
+        assert_eq!(
+            roots.iter().map(|&r| self.size[r]).sum::<usize>(),
+            self.len()
+        );
+        for i in 0..self.len() {
+            // Every chain of parents reaches a root within the number of items.
+            let mut j = i;
+            for _ in 0..self.len() {
+                j = self.parent[j];
+            }
+            assert_eq!(self.parent[j], j, "item {i} does not reach a root");
+        }
+    }
+}
+
+/// Disjoint sets of arbitrary hashable items.
+///
+/// Items are added when they are first mentioned.
+#[derive(Clone, Debug)]
+pub struct KeyedUnionFind<K> {
+    index: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: UnionFind,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        KeyedUnionFind {
+            index: HashMap::new(),
+            keys: Vec::new(),
+            sets: UnionFind::default(),
+        }
+    }
+}
+
+impl<K> KeyedUnionFind<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        KeyedUnionFind::default()
+    }
+
+    /// Add `k` in a set by itself, if it's not already present.
+    pub fn insert(&mut self, k: &K) {
+        self.index_of(k);
+    }
+
+    fn index_of(&mut self, k: &K) -> usize {
+        if let Some(&i) = self.index.get(k) {
+            return i;
+        }
+        let i = self.sets.add();
+        self.index.insert(k.clone(), i);
+        self.keys.push(k.clone());
+        i
+    }
+
+    /// True if `k` has been added.
+    pub fn contains(&self, k: &K) -> bool {
+        self.index.contains_key(k)
+    }
+
+    /// The number of items in all sets.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The number of distinct sets.
+    pub fn component_count(&self) -> usize {
+        self.sets.component_count()
+    }
+
+    /// Join the sets containing `a` and `b`, adding either if they're not
+    /// already present.
+    ///
+    /// Returns false if they were already in the same set.
+    pub fn union(&mut self, a: &K, b: &K) -> bool {
+        let (ia, ib) = (self.index_of(a), self.index_of(b));
+        self.sets.union(ia, ib)
+    }
+
+    /// Return the representative of the set containing `k`, or None if `k`
+    /// has not been added.
+    pub fn find(&mut self, k: &K) -> Option<&K> {
+        let i = *self.index.get(k)?;
+        let r = self.sets.find(i);
+        Some(&self.keys[r])
+    }
+
+    /// True if `a` and `b` have both been added and are in the same set.
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&ia), Some(&ib)) => self.sets.same(ia, ib),
+            _ => false,
+        }
+    }
+
+    /// The number of items in the set containing `k`, or 0 if it has not been added.
+    pub fn size_of(&mut self, k: &K) -> usize {
+        match self.index.get(k) {
+            Some(&i) => self.sets.size_of(i),
+            None => 0,
+        }
+    }
+
+    /// Return the size of every set, in no particular order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.sets.component_sizes()
+    }
+
+    /// Return the items in every set.
+    ///
+    /// Items within each set, and the sets themselves, are in the order
+    /// the items were first added.
+    pub fn components(&mut self) -> Vec<Vec<K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| self.keys[i].clone()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn new_sets_are_separate() {
+        let mut uf = UnionFind::new(4);
+        assert_eq!(uf.len(), 4);
+        assert_eq!(uf.component_count(), 4);
+        assert!(!uf.same(0, 1));
+        assert_eq!(uf.size_of(2), 1);
+        assert_eq!(uf.components(), [[0], [1], [2], [3]]);
+        assert!(UnionFind::default().is_empty());
+    }
+
+    #[test]
+    fn union_joins_sets() {
+        let mut uf = UnionFind::new(5);
+        assert!(uf.union(3, 1));
+        assert!(uf.union(1, 4));
+        assert!(!uf.union(4, 3));
+        assert!(uf.same(3, 4));
+        assert_eq!(uf.size_of(4), 3);
+        assert_eq!(uf.component_count(), 3);
+        assert_eq!(uf.components(), [vec![0], vec![1, 3, 4], vec![2]]);
+        let mut sizes = uf.component_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 3]);
+        let new = uf.add();
+        assert_eq!(new, 5);
+        assert_eq!(uf.component_count(), 4);
+    }
+
+    #[test]
+    fn keyed_sets() {
+        let mut uf = KeyedUnionFind::new();
+        assert!(uf.union(&"b", &"a"));
+        uf.insert(&"c");
+        assert!(uf.union(&"d", &"a"));
+        assert!(!uf.union(&"d", &"b"));
+        assert_eq!(uf.len(), 4);
+        assert_eq!(uf.component_count(), 2);
+        assert!(uf.same(&"b", &"d"));
+        assert!(!uf.same(&"b", &"c"));
+        assert!(!uf.same(&"b", &"zzz"));
+        assert_eq!(uf.find(&"zzz"), None);
+        let root = uf.find(&"a").copied();
+        assert_eq!(uf.find(&"d").copied(), root);
+        assert_eq!(uf.size_of(&"a"), 3);
+        assert_eq!(uf.size_of(&"zzz"), 0);
+        assert!(uf.contains(&"c"));
+        assert_eq!(uf.components(), [vec!["b", "a", "d"], vec!["c"]]);
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_relabelling(n in 1..30usize, joins in prop::collection::vec((0..30usize, 0..30usize), 0..40)) {
+            // A simple model: every item has a label, and joining relabels one whole set.
+            let mut label: Vec<usize> = (0..n).collect();
+            let mut uf = UnionFind::new(n);
+            for (a, b) in joins {
+                let (a, b) = (a % n, b % n);
+                let (la, lb) = (label[a], label[b]);
+                prop_assert_eq!(uf.union(a, b), la != lb);
+                label.iter_mut().filter(|l| **l == lb).for_each(|l| *l = la);
+                uf.assert_valid();
+            }
+            for a in 0..n {
+                for b in 0..n {
+                    prop_assert_eq!(uf.same(a, b), label[a] == label[b]);
+                }
+                prop_assert_eq!(uf.size_of(a), label.iter().filter(|l| **l == label[a]).count());
+            }
+            let mut distinct = label.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            prop_assert_eq!(uf.component_count(), distinct.len());
+            prop_assert_eq!(uf.component_sizes().iter().sum::<usize>(), n);
+            let components = uf.components();
+            prop_assert_eq!(components.len(), distinct.len());
+            prop_assert!(components.iter().all(|c| c.iter().all(|i| label[*i] == label[c[0]])));
+        }
+    }
+}
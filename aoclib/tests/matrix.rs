@@ -114,6 +114,87 @@ fn from_linear_iter() {
     assert_eq!(m.height(), 5);
 }
 
+#[test]
+fn from_rows() {
+    let mut b = Matrix::from_rows();
+    b.add_row(&[1, 2, 3]);
+    b.add_row(&[4, 5, 6]);
+    b.add_row(&[7, 8, 9]);
+    let m = b.finish();
+    assert_eq!(m.width(), 3);
+    assert_eq!(m.height(), 3);
+    assert_eq!(m[point(0, 0)], 1);
+    assert_eq!(m[point(2, 0)], 3);
+    assert_eq!(m[point(2, 2)], 9);
+}
+
+#[test]
+#[should_panic]
+fn from_rows_ragged() {
+    let mut b = Matrix::from_rows();
+    b.add_row(&[1, 2, 3]);
+    b.add_row(&[4, 5]);
+}
+
+#[test]
+fn neighbor8_values() {
+    let m = Matrix::from_linear_iter(0..9, 3);
+    let mut corner = m.neighbor8_values(point(0, 0));
+    corner.sort_unstable();
+    assert_eq!(corner, [1, 3, 4]);
+    let mut center = m.neighbor8_values(point(1, 1));
+    center.sort_unstable();
+    assert_eq!(center, [0, 1, 2, 3, 5, 6, 7, 8]);
+}
+
+#[test]
+fn shortest_path_from_nearest_start() {
+    let m = Matrix::from_string_lines(
+        "\
+a....
+.###.
+....a
+##.##
+..e..
+",
+    );
+    let open = |_: &char, c: &char| *c != '#';
+    let end = m.find_single_value(&'e');
+    let path = m
+        .shortest_path_from_any(m.find_values(&'a'), end, open)
+        .unwrap();
+    assert_eq!(path.distance(), 4);
+    assert_eq!(*path.origin(), point(4, 2));
+    assert_eq!(*path.final_point(), end);
+}
+
+#[test]
+fn distances_in_maze() {
+    let m = Matrix::from_string_lines(
+        "\
+#######
+#a...b#
+#.###.#
+#c..#.#
+#######
+",
+    );
+    let open = |_: &char, c: &char| *c != '#';
+    let a = m.find_single_value(&'a');
+    let b = m.find_single_value(&'b');
+    let c = m.find_single_value(&'c');
+    let dm = m.distances_from(a, open);
+    assert_eq!(dm.distance(&b), Some(4));
+    assert_eq!(dm.distance(&c), Some(2));
+    assert_eq!(dm.distance(&point(5, 3)), Some(6));
+    assert_eq!(dm.len(), 11);
+
+    let pairs = m.pairwise_distances(&[a, b, c], open);
+    assert_eq!(pairs.len(), 6);
+    assert_eq!(pairs[&(b, c)], 6);
+    assert_eq!(pairs[&(c, b)], 6);
+}
+
 proptest! {
     #[test]
     fn basic_proptest(content in prop::collection::vec(0..100u32, 4), x in 0usize..2, y in 0usize..2) {